@@ -64,6 +64,8 @@ impl_misc_api_event_type!(
     ToggleAllKVRequest,
     ToggleAllKVResponse,
     MerchantAccountDeleteResponse,
+    MerchantTokensPurgeResponse,
+    MerchantTokenCountsResponse,
     MerchantAccountUpdate,
     CardInfoResponse,
     CreateApiKeyResponse,
@@ -974,6 +974,13 @@ pub struct TokenizePayloadRequest {
     pub value2: String,
     pub lookup_key: String,
     pub service_name: String,
+    /// Tenant the token was created for, absent for tokens created before tenants were tagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Unix timestamp of when the token was created, absent for tokens created before it was
+    /// recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -1041,9 +1048,89 @@ pub struct TokenizedCardValue2 {
     pub external_id: Option<String>,
     pub customer_id: Option<id_type::CustomerId>,
     pub payment_method_id: Option<String>,
+    /// Billing address stored alongside the card, so that re-authorizations use the same address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub billing_address: Option<payments::Address>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+/// How much of the payment method data held by tokenized payment methods is shown in their
+/// `Debug` output, and so in logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizedDataRedactionPolicy {
+    /// Only the name of the struct is shown
+    #[default]
+    Full,
+    /// The structure of the data is shown, with every string value masked down to at most its
+    /// last four characters
+    Masked,
+}
+
+static TOKENIZED_DATA_REDACTION_POLICY: std::sync::OnceLock<TokenizedDataRedactionPolicy> =
+    std::sync::OnceLock::new();
+
+/// Sets the redaction policy of tokenized payment method data for the lifetime of the process.
+/// Returns `false` if a policy was already set, in which case it is left unchanged.
+pub fn set_tokenized_data_redaction_policy(policy: TokenizedDataRedactionPolicy) -> bool {
+    TOKENIZED_DATA_REDACTION_POLICY.set(policy).is_ok()
+}
+
+fn fmt_tokenized_data<T: serde::Serialize>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    data: &T,
+) -> std::fmt::Result {
+    let policy = TOKENIZED_DATA_REDACTION_POLICY
+        .get()
+        .copied()
+        .unwrap_or_default();
+    let masked_data = match policy {
+        TokenizedDataRedactionPolicy::Full => None,
+        TokenizedDataRedactionPolicy::Masked => {
+            serde_json::to_value(data).ok().map(mask_tokenized_value)
+        }
+    };
+
+    match masked_data {
+        Some(masked_data) => f
+            .debug_struct(name)
+            .field("data", &format_args!("{masked_data}"))
+            .finish(),
+        None => f.debug_struct(name).finish_non_exhaustive(),
+    }
+}
+
+/// Masks every string in the serialized payment method data, since plain `String` fields such as
+/// wallet tokens are as sensitive as the ones wrapped in `Secret`. Strings of at least eight
+/// characters keep their last four, shorter ones are masked entirely.
+fn mask_tokenized_value(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(value) => {
+            let char_count = value.chars().count();
+            let masked = if char_count >= 8 {
+                let last_four = value.chars().skip(char_count - 4).collect::<String>();
+                format!("****{last_four}")
+            } else {
+                "****".to_string()
+            };
+            serde_json::Value::String(masked)
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(mask_tokenized_value).collect())
+        }
+        serde_json::Value::Object(fields) => serde_json::Value::Object(
+            fields
+                .into_iter()
+                .map(|(field, value)| (field, mask_tokenized_value(value)))
+                .collect(),
+        ),
+        value @ (serde_json::Value::Null
+        | serde_json::Value::Bool(_)
+        | serde_json::Value::Number(_)) => value,
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct TokenizedWalletValue1 {
     pub data: payments::WalletData,
 }
@@ -1053,22 +1140,69 @@ pub struct TokenizedWalletValue2 {
     pub customer_id: Option<id_type::CustomerId>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+impl std::fmt::Debug for TokenizedWalletValue1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_tokenized_data(f, "TokenizedWalletValue1", &self.data)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct TokenizedBankTransferValue1 {
     pub data: payments::BankTransferData,
 }
 
+impl std::fmt::Debug for TokenizedBankTransferValue1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_tokenized_data(f, "TokenizedBankTransferValue1", &self.data)
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct TokenizedBankTransferValue2 {
     pub customer_id: Option<id_type::CustomerId>,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct TokenizedBankRedirectValue1 {
     pub data: payments::BankRedirectData,
 }
 
+impl std::fmt::Debug for TokenizedBankRedirectValue1 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_tokenized_data(f, "TokenizedBankRedirectValue1", &self.data)
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct TokenizedBankRedirectValue2 {
     pub customer_id: Option<id_type::CustomerId>,
 }
+
+#[cfg(test)]
+mod tokenized_data_redaction_tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_tokenized_value() {
+        let value = serde_json::json!({
+            "google_pay": {
+                "tokenization_data": { "token": "gpay-token-value-1234" },
+                "type": "CARD",
+            },
+            "iban": "DE89370400440532013000",
+            "amounts": [100, null, true],
+        });
+
+        assert_eq!(
+            mask_tokenized_value(value),
+            serde_json::json!({
+                "google_pay": {
+                    "tokenization_data": { "token": "****1234" },
+                    "type": "****",
+                },
+                "iban": "****3000",
+                "amounts": [100, null, true],
+            })
+        );
+    }
+}
@@ -4568,6 +4568,9 @@ pub struct PaymentsCancelRequest {
     pub payment_id: String,
     /// The reason for the payment cancel
     pub cancellation_reason: Option<String>,
+    /// The amount to reverse for a partial reversal of the authorization, in the lowest denomination of the currency. The full authorization is reversed if not provided. Rejected for connectors that do not support partial reversals.
+    #[schema(value_type = Option<i64>, example = 6540)]
+    pub amount_to_reverse: Option<MinorUnit>,
     /// Merchant connector details used to make payments.
     #[schema(value_type = Option<MerchantConnectorDetailsWrap>)]
     pub merchant_connector_details: Option<admin::MerchantConnectorDetailsWrap>,
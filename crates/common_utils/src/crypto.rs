@@ -295,6 +295,63 @@ impl DecodeMessage for GcmAes256 {
     }
 }
 
+/// Represents the ChaCha20-Poly1305 algorithm
+#[derive(Debug)]
+pub struct ChaCha20Poly1305;
+
+impl EncodeMessage for ChaCha20Poly1305 {
+    fn encode_message(
+        &self,
+        secret: &[u8],
+        msg: &[u8],
+    ) -> CustomResult<Vec<u8>, errors::CryptoError> {
+        let nonce_sequence =
+            NonceSequence::new().change_context(errors::CryptoError::EncodingFailed)?;
+        let current_nonce = nonce_sequence.current();
+        let key = UnboundKey::new(&aead::CHACHA20_POLY1305, secret)
+            .change_context(errors::CryptoError::EncodingFailed)?;
+        let mut key = SealingKey::new(key, nonce_sequence);
+        let mut in_out = msg.to_vec();
+
+        key.seal_in_place_append_tag(aead::Aad::empty(), &mut in_out)
+            .change_context(errors::CryptoError::EncodingFailed)?;
+        in_out.splice(0..0, current_nonce);
+
+        Ok(in_out)
+    }
+}
+
+impl DecodeMessage for ChaCha20Poly1305 {
+    fn decode_message(
+        &self,
+        secret: &[u8],
+        msg: Secret<Vec<u8>, EncryptionStrategy>,
+    ) -> CustomResult<Vec<u8>, errors::CryptoError> {
+        let msg = msg.expose();
+        let key = UnboundKey::new(&aead::CHACHA20_POLY1305, secret)
+            .change_context(errors::CryptoError::DecodingFailed)?;
+
+        let nonce_sequence = NonceSequence::from_bytes(
+            <[u8; aead::NONCE_LEN]>::try_from(
+                msg.get(..aead::NONCE_LEN)
+                    .ok_or(errors::CryptoError::DecodingFailed)
+                    .attach_printable("Failed to read the nonce form the encrypted ciphertext")?,
+            )
+            .change_context(errors::CryptoError::DecodingFailed)?,
+        );
+
+        let mut key = OpeningKey::new(key, nonce_sequence);
+        let mut binding = msg;
+        let output = binding.as_mut_slice();
+
+        let result = key
+            .open_within(aead::Aad::empty(), output, aead::NONCE_LEN..)
+            .change_context(errors::CryptoError::DecodingFailed)?;
+
+        Ok(result.to_vec())
+    }
+}
+
 /// Secure Hash Algorithm 512
 #[derive(Debug)]
 pub struct Sha512;
@@ -617,6 +674,29 @@ mod crypto_tests {
         );
     }
 
+    #[test]
+    fn test_chacha20_poly1305_encode_message() {
+        let message = r#"{"type":"PAYMENT"}"#.as_bytes();
+        let secret =
+            hex::decode("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f")
+                .expect("Secret decoding");
+        let algorithm = super::ChaCha20Poly1305;
+
+        let encoded_message = algorithm
+            .encode_message(&secret, message)
+            .expect("Encoded message and tag");
+
+        assert_eq!(
+            algorithm
+                .decode_message(&secret, encoded_message.clone().into())
+                .expect("Decode Failed"),
+            message
+        );
+        assert!(super::GcmAes256
+            .decode_message(&secret, encoded_message.into())
+            .is_err());
+    }
+
     #[test]
     fn test_gcm_aes_256_decode_message() {
         // Inputs taken from AES GCM test vectors provided by NIST
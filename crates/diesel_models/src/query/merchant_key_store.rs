@@ -1,7 +1,11 @@
-use diesel::{associations::HasTable, ExpressionMethods};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+use error_stack::ResultExt;
+use time::PrimitiveDateTime;
 
 use super::generics;
 use crate::{
+    errors::DatabaseError,
     merchant_key_store::{MerchantKeyStore, MerchantKeyStoreNew},
     schema::merchant_key_store::dsl,
     PgPooledConn, StorageResult,
@@ -54,4 +58,18 @@ impl MerchantKeyStore {
         )
         .await
     }
+
+    /// Lists the merchant id and creation time of the key stores, without loading the keys
+    pub async fn list_key_store_metadata(
+        conn: &PgPooledConn,
+        merchant_ids: Vec<String>,
+    ) -> StorageResult<Vec<(String, PrimitiveDateTime)>> {
+        Self::table()
+            .select((dsl::merchant_id, dsl::created_at))
+            .filter(dsl::merchant_id.eq_any(merchant_ids))
+            .load_async::<(String, PrimitiveDateTime)>(conn)
+            .await
+            .change_context(DatabaseError::Others)
+            .attach_printable("Error listing merchant key store metadata")
+    }
 }
@@ -89,6 +89,30 @@ impl ProcessTracker {
         .await
     }
 
+    #[instrument(skip(conn))]
+    pub async fn find_processes_by_runner_business_status(
+        conn: &PgPooledConn,
+        runner: &str,
+        business_status: &str,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::runner
+                .eq(runner.to_owned())
+                .and(dsl::business_status.eq(business_status.to_owned())),
+            limit,
+            None,
+            None,
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_processes_to_clean(
         conn: &PgPooledConn,
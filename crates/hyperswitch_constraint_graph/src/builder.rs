@@ -28,6 +28,57 @@ impl From<DomainId> for DomainIdOrIdentifier<'_> {
         Self::DomainId(value)
     }
 }
+
+/// Refers to a node either by its id or, for value nodes, by its value
+#[derive(Debug, Clone)]
+pub enum NodeIdOrValue<V: ValueNode> {
+    NodeId(NodeId),
+    Value(NodeValue<V>),
+}
+
+impl<V: ValueNode> From<NodeId> for NodeIdOrValue<V> {
+    fn from(value: NodeId) -> Self {
+        Self::NodeId(value)
+    }
+}
+
+impl<V: ValueNode> From<NodeValue<V>> for NodeIdOrValue<V> {
+    fn from(value: NodeValue<V>) -> Self {
+        Self::Value(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EdgeDiff<V: ValueNode> {
+    pub pred: NodeIdOrValue<V>,
+    pub succ: NodeIdOrValue<V>,
+    pub strength: Strength,
+    pub relation: Relation,
+    pub domain: Option<DomainId>,
+}
+
+/// A set of changes to apply to an existing builder.
+///
+/// Removals are applied before additions, so an edge can be replaced by removing it and adding
+/// it again with a different strength or relation in the same diff.
+#[derive(Debug, Clone)]
+pub struct GraphDiff<V: ValueNode> {
+    pub add_value_nodes: Vec<(NodeValue<V>, Option<&'static str>)>,
+    pub add_edges: Vec<EdgeDiff<V>>,
+    pub remove_value_nodes: Vec<NodeValue<V>>,
+    pub remove_edges: Vec<(NodeIdOrValue<V>, NodeIdOrValue<V>, Option<DomainId>)>,
+}
+
+impl<V: ValueNode> Default for GraphDiff<V> {
+    fn default() -> Self {
+        Self {
+            add_value_nodes: Vec::new(),
+            add_edges: Vec::new(),
+            remove_value_nodes: Vec::new(),
+            remove_edges: Vec::new(),
+        }
+    }
+}
 #[derive(Debug)]
 pub struct ConstraintGraphBuilder<'a, V: ValueNode> {
     domain: DenseMap<DomainId, DomainInfo<'a>>,
@@ -46,15 +97,21 @@ where
     V: ValueNode,
 {
     pub fn new() -> Self {
+        Self::with_capacity(0, 0, 0)
+    }
+
+    /// Pre-sizes the internal maps for the expected number of nodes, edges and domains, to avoid
+    /// reallocating while building large graphs
+    pub fn with_capacity(nodes: usize, edges: usize, domains: usize) -> Self {
         Self {
-            domain: DenseMap::new(),
-            nodes: DenseMap::new(),
-            edges: DenseMap::new(),
-            domain_identifier_map: FxHashMap::default(),
-            value_map: FxHashMap::default(),
-            edges_map: FxHashMap::default(),
-            node_info: DenseMap::new(),
-            node_metadata: DenseMap::new(),
+            domain: DenseMap::with_capacity(domains),
+            nodes: DenseMap::with_capacity(nodes),
+            edges: DenseMap::with_capacity(edges),
+            domain_identifier_map: FxHashMap::with_capacity_and_hasher(domains, Default::default()),
+            value_map: FxHashMap::with_capacity_and_hasher(nodes, Default::default()),
+            edges_map: FxHashMap::with_capacity_and_hasher(edges, Default::default()),
+            node_info: DenseMap::with_capacity(nodes),
+            node_metadata: DenseMap::with_capacity(nodes),
         }
     }
 
@@ -133,6 +190,9 @@ where
     ) -> Result<EdgeId, GraphError<V>> {
         self.ensure_node_exists(pred_id)?;
         self.ensure_node_exists(succ_id)?;
+        if pred_id == succ_id {
+            return Err(GraphError::SelfEdge);
+        }
         let domain_id = domain
             .map(|d| match d.into() {
                 DomainIdOrIdentifier::DomainIdentifier(ident) => {
@@ -155,6 +215,7 @@ where
                         pred: pred_id,
                         succ: succ_id,
                         domain: domain_id,
+                        removed: false,
                     });
                     self.edges_map
                         .insert((pred_id, succ_id, domain_id), edge_id);
@@ -265,8 +326,126 @@ where
         Ok(node_id)
     }
 
+    /// Gates the node behind a feature flag, so that it is treated as absent while the flag is
+    /// disabled in the checking context. Value nodes are shared by every rule using their value,
+    /// so the flag applies to all of them.
+    pub fn set_feature_flag(
+        &mut self,
+        node_id: NodeId,
+        feature_flag: impl Into<String>,
+    ) -> Result<(), GraphError<V>> {
+        let node = self
+            .nodes
+            .get_mut(node_id)
+            .ok_or(GraphError::NodeNotFound)?;
+        node.feature_flag = Some(feature_flag.into());
+        Ok(())
+    }
+
+    /// Applies a [`GraphDiff`] to the builder.
+    ///
+    /// Every operation is validated the same way as the corresponding `make_*` call. Removed
+    /// nodes and edges are detached from the graph and marked as removed but keep their ids, so
+    /// ids handed out earlier stay valid, while referring to a removed node fails with
+    /// [`GraphError::NodeNotFound`]. If an error is returned the builder may be partially updated and should be
+    /// discarded.
+    pub fn apply_diff(&mut self, diff: GraphDiff<V>) -> Result<(), GraphError<V>> {
+        for (pred, succ, domain_id) in diff.remove_edges {
+            let pred_id = self.resolve_node(pred)?;
+            let succ_id = self.resolve_node(succ)?;
+            self.remove_edge(pred_id, succ_id, domain_id)?;
+        }
+
+        for value in diff.remove_value_nodes {
+            self.remove_value_node(&value)?;
+        }
+
+        for (value, info) in diff.add_value_nodes {
+            self.make_value_node::<()>(value, info, None);
+        }
+
+        for edge in diff.add_edges {
+            let pred_id = self.resolve_node(edge.pred)?;
+            let succ_id = self.resolve_node(edge.succ)?;
+            self.make_edge(pred_id, succ_id, edge.strength, edge.relation, edge.domain)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_node(&self, node: NodeIdOrValue<V>) -> Result<NodeId, GraphError<V>> {
+        match node {
+            NodeIdOrValue::NodeId(node_id) => {
+                self.ensure_node_exists(node_id)?;
+                Ok(node_id)
+            }
+            NodeIdOrValue::Value(value) => self
+                .value_map
+                .get(&value)
+                .copied()
+                .ok_or(GraphError::NodeNotFound),
+        }
+    }
+
+    fn remove_edge(
+        &mut self,
+        pred_id: NodeId,
+        succ_id: NodeId,
+        domain_id: Option<DomainId>,
+    ) -> Result<(), GraphError<V>> {
+        let edge_id = self
+            .edges_map
+            .remove(&(pred_id, succ_id, domain_id))
+            .ok_or(GraphError::EdgeNotFound)?;
+        if let Some(edge) = self.edges.get_mut(edge_id) {
+            edge.removed = true;
+        }
+
+        let pred = self
+            .nodes
+            .get_mut(pred_id)
+            .ok_or(GraphError::NodeNotFound)?;
+        pred.succs.retain(|id| *id != edge_id);
+
+        let succ = self
+            .nodes
+            .get_mut(succ_id)
+            .ok_or(GraphError::NodeNotFound)?;
+        succ.preds.retain(|id| *id != edge_id);
+
+        Ok(())
+    }
+
+    fn remove_value_node(&mut self, value: &NodeValue<V>) -> Result<(), GraphError<V>> {
+        let node_id = self
+            .value_map
+            .get(value)
+            .copied()
+            .ok_or(GraphError::NodeNotFound)?;
+        let node = self.nodes.get(node_id).ok_or(GraphError::NodeNotFound)?;
+
+        let attached_edges = node
+            .preds
+            .iter()
+            .chain(node.succs.iter())
+            .filter_map(|edge_id| self.edges.get(*edge_id))
+            .map(|edge| (edge.pred, edge.succ, edge.domain))
+            .collect::<FxHashSet<_>>();
+
+        for (pred_id, succ_id, domain_id) in attached_edges {
+            self.remove_edge(pred_id, succ_id, domain_id)?;
+        }
+
+        self.value_map.remove(value);
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.removed = true;
+        }
+
+        Ok(())
+    }
+
     fn ensure_node_exists(&self, id: NodeId) -> Result<(), GraphError<V>> {
-        if self.nodes.contains_key(id) {
+        if self.nodes.get(id).is_some_and(|node| !node.removed) {
             Ok(())
         } else {
             Err(GraphError::NodeNotFound)
@@ -281,3 +460,106 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize)]
+    struct TestKey;
+
+    impl crate::types::KeyNode for TestKey {}
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize)]
+    struct TestValue(u8);
+
+    impl ValueNode for TestValue {
+        type Key = TestKey;
+
+        fn get_key(&self) -> Self::Key {
+            TestKey
+        }
+    }
+
+    #[test]
+    fn test_apply_diff_add_and_remove() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let one = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let two = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        builder
+            .make_edge(
+                one,
+                two,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .expect("edge creation");
+
+        let diff = GraphDiff {
+            add_value_nodes: vec![(TestValue(3).into(), None)],
+            add_edges: vec![EdgeDiff {
+                pred: one.into(),
+                succ: NodeValue::from(TestValue(3)).into(),
+                strength: Strength::Normal,
+                relation: Relation::Negative,
+                domain: None,
+            }],
+            remove_value_nodes: vec![TestValue(2).into()],
+            remove_edges: Vec::new(),
+        };
+        builder.apply_diff(diff).expect("diff application");
+
+        let three = builder.value_map[&TestValue(3).into()];
+        assert!(!builder.value_map.contains_key(&TestValue(2).into()));
+        assert!(!builder.edges_map.contains_key(&(one, two, None)));
+        assert!(builder.edges_map.contains_key(&(one, three, None)));
+
+        let one_node = builder.nodes.get(one).expect("node one");
+        assert_eq!(one_node.succs.len(), 1);
+        assert!(builder.nodes.get(two).expect("node two").preds.is_empty());
+
+        let invalid_diff = GraphDiff {
+            remove_edges: vec![(three.into(), one.into(), None)],
+            ..GraphDiff::default()
+        };
+        assert!(matches!(
+            builder.apply_diff(invalid_diff),
+            Err(GraphError::EdgeNotFound)
+        ));
+
+        let removed_node_diff = GraphDiff {
+            remove_edges: vec![(one.into(), two.into(), None)],
+            ..GraphDiff::default()
+        };
+        assert!(matches!(
+            builder.apply_diff(removed_node_diff),
+            Err(GraphError::NodeNotFound)
+        ));
+
+        let graph = builder.build();
+        assert_eq!(graph.live_nodes().count(), 2);
+        assert_eq!(graph.live_edges().count(), 1);
+    }
+
+    #[test]
+    fn test_make_edge_rejects_self_edge() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let one = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+
+        assert!(matches!(
+            builder.make_edge(
+                one,
+                one,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            ),
+            Err(GraphError::SelfEdge)
+        ));
+        assert!(builder.edges_map.is_empty());
+        assert!(builder.nodes.get(one).expect("node one").succs.is_empty());
+    }
+}
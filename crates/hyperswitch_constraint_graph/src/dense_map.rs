@@ -30,11 +30,25 @@ pub struct DenseMap<K, V> {
 
 impl<K, V> DenseMap<K, V> {
     pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            data: Vec::new(),
+            data: Vec::with_capacity(capacity),
             _marker: PhantomData,
         }
     }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
 }
 
 impl<K, V> Default for DenseMap<K, V> {
@@ -17,18 +17,21 @@ pub enum AnalysisTrace<V: ValueNode> {
         relation: Relation,
         predecessors: Option<ValueTracePredecessor<V>>,
         info: Option<&'static str>,
+        #[serde(serialize_with = "crate::types::serialize_redacted_metadata")]
         metadata: Option<Arc<dyn Metadata>>,
     },
 
     AllAggregation {
         unsatisfied: Vec<Weak<AnalysisTrace<V>>>,
         info: Option<&'static str>,
+        #[serde(serialize_with = "crate::types::serialize_redacted_metadata")]
         metadata: Option<Arc<dyn Metadata>>,
     },
 
     AnyAggregation {
         unsatisfied: Vec<Weak<AnalysisTrace<V>>>,
         info: Option<&'static str>,
+        #[serde(serialize_with = "crate::types::serialize_redacted_metadata")]
         metadata: Option<Arc<dyn Metadata>>,
     },
 
@@ -37,6 +40,7 @@ pub enum AnalysisTrace<V: ValueNode> {
         found: Option<V>,
         relation: Relation,
         info: Option<&'static str>,
+        #[serde(serialize_with = "crate::types::serialize_redacted_metadata")]
         metadata: Option<Arc<dyn Metadata>>,
     },
     Contradiction {
@@ -44,6 +48,83 @@ pub enum AnalysisTrace<V: ValueNode> {
     },
 }
 
+/// A node which could not be satisfied during analysis, along with the info and metadata it was
+/// created with. Metadata is serialized through its redacted form.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FailedNode<V: ValueNode> {
+    /// `None` for aggregator nodes
+    pub value: Option<NodeValue<V>>,
+    pub info: Option<&'static str>,
+    #[serde(serialize_with = "crate::types::serialize_redacted_metadata")]
+    pub metadata: Option<Arc<dyn Metadata>>,
+}
+
+impl<V: ValueNode> AnalysisTrace<V> {
+    /// Flattens the trace into the list of nodes that failed, outermost node first
+    pub fn get_failed_nodes(&self) -> Vec<FailedNode<V>> {
+        let mut failed_nodes = Vec::new();
+        self.collect_failed_nodes(&mut failed_nodes);
+        failed_nodes
+    }
+
+    fn collect_failed_nodes(&self, failed_nodes: &mut Vec<FailedNode<V>>) {
+        let predecessors: Vec<&Weak<Self>> = match self {
+            Self::Value {
+                value,
+                predecessors,
+                info,
+                metadata,
+                ..
+            } => {
+                failed_nodes.push(FailedNode {
+                    value: Some(value.clone()),
+                    info: *info,
+                    metadata: metadata.clone(),
+                });
+                match predecessors {
+                    Some(ValueTracePredecessor::Mandatory(predecessor)) => {
+                        vec![predecessor.as_ref()]
+                    }
+                    Some(ValueTracePredecessor::OneOf(predecessors)) => {
+                        predecessors.iter().collect()
+                    }
+                    None => Vec::new(),
+                }
+            }
+            Self::AllAggregation {
+                unsatisfied,
+                info,
+                metadata,
+            }
+            | Self::AnyAggregation {
+                unsatisfied,
+                info,
+                metadata,
+            } => {
+                failed_nodes.push(FailedNode {
+                    value: None,
+                    info: *info,
+                    metadata: metadata.clone(),
+                });
+                unsatisfied.iter().collect()
+            }
+            Self::InAggregation { info, metadata, .. } => {
+                failed_nodes.push(FailedNode {
+                    value: None,
+                    info: *info,
+                    metadata: metadata.clone(),
+                });
+                Vec::new()
+            }
+            Self::Contradiction { .. } => Vec::new(),
+        };
+
+        for predecessor in predecessors.into_iter().filter_map(Weak::upgrade) {
+            predecessor.collect_failed_nodes(failed_nodes);
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, thiserror::Error)]
 #[serde(tag = "type", content = "info", rename_all = "snake_case")]
 pub enum GraphError<V: ValueNode> {
@@ -53,6 +134,8 @@ pub enum GraphError<V: ValueNode> {
     ConflictingEdgeCreated,
     #[error("Cycle detected in graph")]
     CycleDetected,
+    #[error("Attempted to create an edge from a node to itself")]
+    SelfEdge,
     #[error("Domain wasn't found in the Graph")]
     DomainNotFound,
     #[error("Malformed Graph: {reason}")]
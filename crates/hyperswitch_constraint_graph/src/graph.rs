@@ -5,7 +5,7 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use crate::{
     builder,
     dense_map::DenseMap,
-    error::{self, AnalysisTrace, GraphError},
+    error::{self, AnalysisTrace, FailedNode, GraphError},
     types::{
         CheckingContext, CycleCheck, DomainId, DomainIdentifier, DomainInfo, Edge, EdgeId,
         Memoization, Metadata, Node, NodeId, NodeType, NodeValue, Relation, RelationResolution,
@@ -25,6 +25,13 @@ struct CheckNodeContext<'a, V: ValueNode, C: CheckingContext<Value = V>> {
     domains: Option<&'a [DomainId]>,
 }
 
+/// Result of evaluating a node, along with the nodes responsible if it was not satisfied
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NodeEvaluation<V: ValueNode> {
+    pub satisfied: bool,
+    pub failed_nodes: Vec<FailedNode<V>>,
+}
+
 #[derive(Debug)]
 pub struct ConstraintGraph<'a, V: ValueNode> {
     pub domain: DenseMap<DomainId, DomainInfo<'a>>,
@@ -100,6 +107,50 @@ where
         )
     }
 
+    /// Checks a node like [`Self::check_node`], but explains a failure through the `node_info`
+    /// and `node_metadata` of every node that could not be satisfied
+    pub fn evaluate_node<C>(
+        &self,
+        ctx: &C,
+        node_id: NodeId,
+        relation: Relation,
+        strength: Strength,
+        domains: Option<&[&str]>,
+    ) -> Result<NodeEvaluation<V>, GraphError<V>>
+    where
+        C: CheckingContext<Value = V>,
+    {
+        // The memo holds the only strong references to the analysis trace, so it has to outlive
+        // the walk over the trace below
+        let mut memo = Memoization::new();
+        let mut cycle_map = CycleCheck::new();
+
+        match self.check_node(
+            ctx,
+            node_id,
+            relation,
+            strength,
+            &mut memo,
+            &mut cycle_map,
+            domains,
+        ) {
+            Ok(()) => Ok(NodeEvaluation {
+                satisfied: true,
+                failed_nodes: Vec::new(),
+            }),
+            Err(err) => {
+                let trace = err.get_analysis_trace()?;
+                Ok(NodeEvaluation {
+                    satisfied: false,
+                    failed_nodes: trace
+                        .upgrade()
+                        .map(|trace| trace.get_failed_nodes())
+                        .unwrap_or_default(),
+                })
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn check_node_inner<C>(
         &self,
@@ -130,6 +181,12 @@ where
                 value.1 = relation_resolve
             });
             Ok(())
+        } else if node
+            .feature_flag
+            .as_deref()
+            .is_some_and(|feature_flag| !ctx.is_feature_enabled(feature_flag))
+        {
+            self.check_disabled_node(node, node_id, relation, strength, memo)
         } else {
             let check_node_context = CheckNodeContext {
                 node,
@@ -155,6 +212,52 @@ where
         }
     }
 
+    /// A node whose feature flag is disabled is absent, so it holds only for a negative relation
+    fn check_disabled_node(
+        &self,
+        node: &Node<V>,
+        node_id: NodeId,
+        relation: Relation,
+        strength: Strength,
+        memo: &mut Memoization<V>,
+    ) -> Result<(), GraphError<V>> {
+        if relation == Relation::Negative {
+            memo.insert((node_id, relation, strength), Ok(()));
+            return Ok(());
+        }
+
+        let info = self.node_info.get(node_id).cloned().flatten();
+        let metadata = self.node_metadata.get(node_id).cloned().flatten();
+        let err = Arc::new(match &node.node_type {
+            NodeType::Value(value) => AnalysisTrace::Value {
+                value: value.clone(),
+                relation,
+                predecessors: None,
+                info,
+                metadata,
+            },
+            NodeType::AllAggregator => AnalysisTrace::AllAggregation {
+                unsatisfied: Vec::new(),
+                info,
+                metadata,
+            },
+            NodeType::AnyAggregator => AnalysisTrace::AnyAggregation {
+                unsatisfied: Vec::new(),
+                info,
+                metadata,
+            },
+            NodeType::InAggregator(expected) => AnalysisTrace::InAggregation {
+                expected: expected.iter().cloned().collect(),
+                found: None,
+                relation,
+                info,
+                metadata,
+            },
+        });
+        memo.insert((node_id, relation, strength), Err(Arc::clone(&err)));
+        Err(GraphError::AnalysisError(Arc::downgrade(&err)))
+    }
+
     fn validate_all_aggregator<C>(
         &self,
         vald: CheckNodeContext<'_, V, C>,
@@ -482,10 +585,20 @@ where
         Ok(())
     }
 
+    /// Nodes of the graph along with their ids, skipping the nodes removed by graph diffs
+    pub fn live_nodes(&self) -> impl Iterator<Item = (NodeId, &Node<V>)> {
+        self.nodes.iter().filter(|(_, node)| !node.removed)
+    }
+
+    /// Edges of the graph, skipping the edges removed by graph diffs
+    pub fn live_edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.values().filter(|edge| !edge.removed)
+    }
+
     pub fn combine<'b>(g1: &'b Self, g2: &'b Self) -> Result<Self, GraphError<V>> {
         let mut node_builder = builder::ConstraintGraphBuilder::new();
-        let mut g1_old2new_id = DenseMap::<NodeId, NodeId>::new();
-        let mut g2_old2new_id = DenseMap::<NodeId, NodeId>::new();
+        let mut g1_old2new_id = FxHashMap::<NodeId, NodeId>::default();
+        let mut g2_old2new_id = FxHashMap::<NodeId, NodeId>::default();
         let mut g1_old2new_domain_id = DenseMap::<DomainId, DomainId>::new();
         let mut g2_old2new_domain_id = DenseMap::<DomainId, DomainId>::new();
 
@@ -501,25 +614,29 @@ where
         let add_node = |node_builder: &mut builder::ConstraintGraphBuilder<'a, V>,
                         node: &Node<V>|
          -> Result<NodeId, GraphError<V>> {
-            match &node.node_type {
+            let node_id = match &node.node_type {
                 NodeType::Value(node_value) => {
-                    Ok(node_builder.make_value_node(node_value.clone(), None, None::<()>))
+                    node_builder.make_value_node(node_value.clone(), None, None::<()>)
                 }
 
                 NodeType::AllAggregator => {
-                    Ok(node_builder.make_all_aggregator(&[], None, None::<()>, None)?)
+                    node_builder.make_all_aggregator(&[], None, None::<()>, None)?
                 }
 
                 NodeType::AnyAggregator => {
-                    Ok(node_builder.make_any_aggregator(&[], None, None::<()>, None)?)
+                    node_builder.make_any_aggregator(&[], None, None::<()>, None)?
                 }
 
-                NodeType::InAggregator(expected) => Ok(node_builder.make_in_aggregator(
+                NodeType::InAggregator(expected) => node_builder.make_in_aggregator(
                     expected.iter().cloned().collect(),
                     None,
                     None::<()>,
-                )?),
+                )?,
+            };
+            if let Some(feature_flag) = &node.feature_flag {
+                node_builder.set_feature_flag(node_id, feature_flag.clone())?;
             }
+            Ok(node_id)
         };
 
         for (_old_domain_id, domain) in g1.domain.iter() {
@@ -532,22 +649,22 @@ where
             g2_old2new_domain_id.push(new_domain_id);
         }
 
-        for (_old_node_id, node) in g1.nodes.iter() {
+        for (old_node_id, node) in g1.live_nodes() {
             let new_node_id = add_node(&mut node_builder, node)?;
-            g1_old2new_id.push(new_node_id);
+            g1_old2new_id.insert(old_node_id, new_node_id);
         }
 
-        for (_old_node_id, node) in g2.nodes.iter() {
+        for (old_node_id, node) in g2.live_nodes() {
             let new_node_id = add_node(&mut node_builder, node)?;
-            g2_old2new_id.push(new_node_id);
+            g2_old2new_id.insert(old_node_id, new_node_id);
         }
 
-        for edge in g1.edges.values() {
+        for edge in g1.live_edges() {
             let new_pred_id = g1_old2new_id
-                .get(edge.pred)
+                .get(&edge.pred)
                 .ok_or(GraphError::NodeNotFound)?;
             let new_succ_id = g1_old2new_id
-                .get(edge.succ)
+                .get(&edge.succ)
                 .ok_or(GraphError::NodeNotFound)?;
             let domain_ident = edge
                 .domain
@@ -564,12 +681,12 @@ where
             )?;
         }
 
-        for edge in g2.edges.values() {
+        for edge in g2.live_edges() {
             let new_pred_id = g2_old2new_id
-                .get(edge.pred)
+                .get(&edge.pred)
                 .ok_or(GraphError::NodeNotFound)?;
             let new_succ_id = g2_old2new_id
-                .get(edge.succ)
+                .get(&edge.succ)
                 .ok_or(GraphError::NodeNotFound)?;
             let domain_ident = edge
                 .domain
@@ -588,6 +705,213 @@ where
 
         Ok(node_builder.build())
     }
+
+    /// Extracts the edges belonging to a single domain, along with the nodes they connect, into a
+    /// standalone graph. Edges from other domains and edges without a domain are left out, and
+    /// node metadata is not carried over.
+    pub fn subgraph_for_domain(
+        &self,
+        domain_identifier: DomainIdentifier<'a>,
+    ) -> Result<Self, GraphError<V>> {
+        let domain_id = self
+            .domain_identifier_map
+            .get(&domain_identifier)
+            .copied()
+            .ok_or(GraphError::DomainNotFound)?;
+        let domain = self
+            .domain
+            .get(domain_id)
+            .ok_or(GraphError::DomainNotFound)?;
+
+        let domain_edges = self
+            .live_edges()
+            .filter(|edge| edge.domain == Some(domain_id))
+            .collect::<Vec<_>>();
+        let domain_node_ids = domain_edges
+            .iter()
+            .flat_map(|edge| [edge.pred, edge.succ])
+            .collect::<FxHashSet<_>>();
+
+        let mut node_builder = builder::ConstraintGraphBuilder::new();
+        node_builder.make_domain(
+            domain.domain_identifier.into_inner(),
+            &domain.domain_description,
+        )?;
+
+        let mut old2new_id = FxHashMap::<NodeId, NodeId>::default();
+        for (old_node_id, node) in self.live_nodes() {
+            if !domain_node_ids.contains(&old_node_id) {
+                continue;
+            }
+
+            let info = self.node_info.get(old_node_id).copied().flatten();
+            let new_node_id = match &node.node_type {
+                NodeType::Value(node_value) => {
+                    node_builder.make_value_node(node_value.clone(), info, None::<()>)
+                }
+
+                NodeType::AllAggregator => {
+                    node_builder.make_all_aggregator(&[], info, None::<()>, None)?
+                }
+
+                NodeType::AnyAggregator => {
+                    node_builder.make_any_aggregator(&[], info, None::<()>, None)?
+                }
+
+                NodeType::InAggregator(expected) => node_builder.make_in_aggregator(
+                    expected.iter().cloned().collect(),
+                    info,
+                    None::<()>,
+                )?,
+            };
+            if let Some(feature_flag) = &node.feature_flag {
+                node_builder.set_feature_flag(new_node_id, feature_flag.clone())?;
+            }
+            old2new_id.insert(old_node_id, new_node_id);
+        }
+
+        for edge in domain_edges {
+            let new_pred_id = old2new_id.get(&edge.pred).ok_or(GraphError::NodeNotFound)?;
+            let new_succ_id = old2new_id.get(&edge.succ).ok_or(GraphError::NodeNotFound)?;
+
+            node_builder.make_edge(
+                *new_pred_id,
+                *new_succ_id,
+                edge.strength,
+                edge.relation,
+                Some(domain.domain_identifier.into_inner()),
+            )?;
+        }
+
+        Ok(node_builder.build())
+    }
+
+    /// Identifiers of the domains a node belongs to, meaning the domains of the edges into and out
+    /// of the node, in the order the edges were added and without duplicates
+    pub fn node_domains(
+        &self,
+        node_id: NodeId,
+    ) -> Result<Vec<DomainIdentifier<'a>>, GraphError<V>> {
+        let node = self.nodes.get(node_id).ok_or(GraphError::NodeNotFound)?;
+        let mut seen_domains = FxHashSet::<DomainId>::default();
+        let mut domains = Vec::new();
+
+        for edge_id in node.preds.iter().chain(node.succs.iter()) {
+            let edge = self.edges.get(*edge_id).ok_or(GraphError::EdgeNotFound)?;
+            if let Some(domain_id) = edge
+                .domain
+                .filter(|domain_id| seen_domains.insert(*domain_id))
+            {
+                let domain = self
+                    .domain
+                    .get(domain_id)
+                    .ok_or(GraphError::DomainNotFound)?;
+                domains.push(domain.domain_identifier);
+            }
+        }
+
+        Ok(domains)
+    }
+
+    /// Propagates the known values through the graph and returns the satisfied output nodes,
+    /// meaning the nodes without successors. A value node holds when its predecessors allow it, as
+    /// in [`Self::check_node`]: every strong edge must hold, as must at least one edge overall.
+    /// Other value nodes must also have their value known, while an output value node is derived
+    /// from its predecessors and only needs to be known when it has none. Edges are followed
+    /// regardless of their domain, and an edge back to a node that is still being evaluated does
+    /// not hold.
+    pub fn satisfied_outputs(&self, known: &FxHashSet<NodeValue<V>>) -> FxHashSet<NodeId> {
+        let mut memo = FxHashMap::<NodeId, bool>::default();
+        let mut visiting = FxHashSet::<NodeId>::default();
+
+        self.live_nodes()
+            .filter(|(_, node)| node.succs.is_empty())
+            .filter(|(node_id, _)| {
+                self.is_node_satisfied(*node_id, known, &mut memo, &mut visiting, &mut false)
+            })
+            .map(|(node_id, _)| node_id)
+            .collect()
+    }
+
+    fn is_node_satisfied(
+        &self,
+        node_id: NodeId,
+        known: &FxHashSet<NodeValue<V>>,
+        memo: &mut FxHashMap<NodeId, bool>,
+        visiting: &mut FxHashSet<NodeId>,
+        cycle_cut: &mut bool,
+    ) -> bool {
+        if let Some(satisfied) = memo.get(&node_id) {
+            return *satisfied;
+        }
+        if !visiting.insert(node_id) {
+            *cycle_cut = true;
+            return false;
+        }
+        // Whether the result depends on a cycle being cut is tracked per node, since such a
+        // result depends on where the evaluation entered the cycle and cannot be memoised
+        let outer_cycle_cut = std::mem::replace(cycle_cut, false);
+
+        let satisfied = self.nodes.get(node_id).is_some_and(|node| {
+            let mut edge_results = node
+                .preds
+                .iter()
+                .filter_map(|edge_id| self.edges.get(*edge_id))
+                .map(|edge| {
+                    let holds = self.is_node_satisfied(edge.pred, known, memo, visiting, cycle_cut)
+                        == bool::from(edge.relation);
+                    (edge.strength, holds)
+                });
+
+            match &node.node_type {
+                NodeType::AllAggregator => edge_results.all(|(_, holds)| holds),
+
+                NodeType::AnyAggregator => {
+                    node.preds.is_empty() || edge_results.any(|(_, holds)| holds)
+                }
+
+                NodeType::InAggregator(expected) => {
+                    let the_key = expected.iter().next().map(ValueNode::get_key);
+                    let mut known_values = known
+                        .iter()
+                        .filter_map(|known_value| match known_value {
+                            NodeValue::Value(value) => Some(value),
+                            NodeValue::Key(_) => None,
+                        })
+                        .filter(|value| the_key.as_ref() == Some(&value.get_key()))
+                        .peekable();
+
+                    known_values.peek().is_some()
+                        && known_values.all(|value| expected.contains(value))
+                }
+
+                NodeType::Value(node_value) => {
+                    let is_known = match node_value {
+                        NodeValue::Key(key) => known.iter().any(|known_value| match known_value {
+                            NodeValue::Key(known_key) => known_key == key,
+                            NodeValue::Value(value) => value.get_key() == *key,
+                        }),
+                        NodeValue::Value(_) => known.contains(node_value),
+                    };
+                    let edge_results = edge_results.collect::<Vec<_>>();
+                    let is_output = node.succs.is_empty() && !edge_results.is_empty();
+
+                    (is_known || is_output)
+                        && edge_results.iter().all(|(strength, holds)| {
+                            *holds || !matches!(strength, Strength::Strong)
+                        })
+                        && (edge_results.is_empty() || edge_results.iter().any(|(_, holds)| *holds))
+                }
+            }
+        });
+
+        visiting.remove(&node_id);
+        if !*cycle_cut {
+            memo.insert(node_id, satisfied);
+        }
+        *cycle_cut |= outer_cycle_cut;
+        satisfied
+    }
 }
 
 #[cfg(feature = "viz")]
@@ -662,11 +986,10 @@ mod viz {
         pub fn get_viz_digraph(&self) -> Graph {
             graph!(
                 strict di id!("constraint_graph"),
-                self.nodes
-                    .iter()
+                self.live_nodes()
                     .map(|(node_id, node)| Self::build_node(node_id, node))
                     .map(Stmt::Node)
-                    .chain(self.edges.values().map(Self::build_edge).map(Stmt::Edge))
+                    .chain(self.live_edges().map(Self::build_edge).map(Stmt::Edge))
                     .collect::<Vec<_>>()
             )
         }
@@ -678,3 +1001,465 @@ mod viz {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use rustc_hash::FxHashSet;
+
+    use super::*;
+    use crate::{builder::ConstraintGraphBuilder, dense_map::EntityId, types::KeyNode};
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize)]
+    struct TestKey;
+
+    impl KeyNode for TestKey {}
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Serialize)]
+    struct TestValue(u8);
+
+    impl ValueNode for TestValue {
+        type Key = TestKey;
+
+        fn get_key(&self) -> Self::Key {
+            TestKey
+        }
+    }
+
+    struct TestContext {
+        values: FxHashSet<NodeValue<TestValue>>,
+        enabled_feature_flags: FxHashSet<&'static str>,
+    }
+
+    impl CheckingContext for TestContext {
+        type Value = TestValue;
+
+        fn from_node_values<L>(vals: impl IntoIterator<Item = L>) -> Self
+        where
+            L: Into<Self::Value>,
+        {
+            Self {
+                values: vals
+                    .into_iter()
+                    .map(|val| NodeValue::Value(val.into()))
+                    .collect(),
+                enabled_feature_flags: FxHashSet::default(),
+            }
+        }
+
+        fn check_presence(&self, value: &NodeValue<Self::Value>, _strength: Strength) -> bool {
+            self.values.contains(value)
+        }
+
+        fn get_values_by_key(&self, _expected: &TestKey) -> Option<Vec<Self::Value>> {
+            None
+        }
+
+        fn is_feature_enabled(&self, feature_flag: &str) -> bool {
+            self.enabled_feature_flags.contains(feature_flag)
+        }
+    }
+
+    #[test]
+    fn test_evaluate_node_explains_failure() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let three_ds = builder.make_value_node::<()>(
+            TestValue(1).into(),
+            Some("3DS required for amount > 100"),
+            None,
+        );
+        let payment = builder.make_value_node::<()>(TestValue(2).into(), Some("payment"), None);
+        builder
+            .make_edge(
+                three_ds,
+                payment,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .expect("edge creation");
+        let graph = builder.build();
+
+        let satisfied = graph
+            .evaluate_node(
+                &TestContext::from_node_values([TestValue(1), TestValue(2)]),
+                payment,
+                Relation::Positive,
+                Strength::Strong,
+                None,
+            )
+            .expect("evaluation");
+        assert!(satisfied.satisfied);
+        assert!(satisfied.failed_nodes.is_empty());
+
+        let disqualified = graph
+            .evaluate_node(
+                &TestContext::from_node_values([TestValue(2)]),
+                payment,
+                Relation::Positive,
+                Strength::Strong,
+                None,
+            )
+            .expect("evaluation");
+        assert!(!disqualified.satisfied);
+        assert!(disqualified
+            .failed_nodes
+            .iter()
+            .any(|node| node.info == Some("3DS required for amount > 100")
+                && node.value == Some(TestValue(1).into())));
+    }
+
+    #[test]
+    fn test_disabled_node_never_satisfies() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let three_ds = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let payment = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        builder
+            .make_edge(
+                three_ds,
+                payment,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .expect("edge creation");
+        builder
+            .set_feature_flag(three_ds, "new_3ds_rule")
+            .expect("feature flag");
+        let graph = builder.build();
+
+        let check = |ctx: &TestContext, node_id, relation| {
+            graph
+                .check_node(
+                    ctx,
+                    node_id,
+                    relation,
+                    Strength::Strong,
+                    &mut Memoization::new(),
+                    &mut CycleCheck::new(),
+                    None,
+                )
+                .is_ok()
+        };
+
+        let flag_disabled = TestContext::from_node_values([TestValue(1), TestValue(2)]);
+        assert!(!check(&flag_disabled, three_ds, Relation::Positive));
+        assert!(!check(&flag_disabled, payment, Relation::Positive));
+        assert!(check(&flag_disabled, three_ds, Relation::Negative));
+
+        let flag_enabled = TestContext {
+            enabled_feature_flags: FxHashSet::from_iter(["new_3ds_rule"]),
+            ..TestContext::from_node_values([TestValue(1), TestValue(2)])
+        };
+        assert!(check(&flag_enabled, three_ds, Relation::Positive));
+        assert!(check(&flag_enabled, payment, Relation::Positive));
+    }
+
+    #[test]
+    fn test_analysis_trace_serializes_redacted_metadata() {
+        #[derive(Debug, serde::Serialize)]
+        struct RuleMetadata {
+            rule_name: String,
+            merchant_threshold: u64,
+        }
+
+        impl Metadata for RuleMetadata {
+            fn redacted_serialize(&self) -> Option<&dyn erased_serde::Serialize> {
+                Some(&self.rule_name)
+            }
+        }
+
+        let trace = |metadata: Arc<dyn Metadata>| AnalysisTrace::Value {
+            value: TestValue(1).into(),
+            relation: Relation::Positive,
+            predecessors: None,
+            info: Some("high value payments require 3ds"),
+            metadata: Some(metadata),
+        };
+
+        let rule_trace = trace(Arc::new(RuleMetadata {
+            rule_name: "high_value_3ds".to_string(),
+            merchant_threshold: 5000,
+        }));
+        let serialized_trace = serde_json::to_value(&rule_trace).unwrap();
+        assert_eq!(serialized_trace["trace"]["metadata"], "high_value_3ds");
+        assert!(!serialized_trace.to_string().contains("merchant_threshold"));
+
+        let serialized_failed_nodes = serde_json::to_value(rule_trace.get_failed_nodes()).unwrap();
+        assert_eq!(serialized_failed_nodes[0]["metadata"], "high_value_3ds");
+        assert!(!serialized_failed_nodes
+            .to_string()
+            .contains("merchant_threshold"));
+
+        let plain_trace = serde_json::to_value(trace(Arc::new(()))).unwrap();
+        assert!(plain_trace["trace"]["metadata"].is_null());
+    }
+
+    #[test]
+    fn test_subgraph_for_domain() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        builder.make_domain("payments", "Payment rules").unwrap();
+        builder.make_domain("payouts", "Payout rules").unwrap();
+        let card = builder.make_value_node::<()>(TestValue(1).into(), Some("card"), None);
+        let three_ds = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        let bank = builder.make_value_node::<()>(TestValue(3).into(), None, None);
+        builder
+            .make_edge(
+                card,
+                three_ds,
+                Strength::Strong,
+                Relation::Positive,
+                Some("payments"),
+            )
+            .unwrap();
+        builder
+            .make_edge(
+                three_ds,
+                bank,
+                Strength::Strong,
+                Relation::Negative,
+                Some("payouts"),
+            )
+            .unwrap();
+        let graph = builder.build();
+
+        let subgraph = graph
+            .subgraph_for_domain(DomainIdentifier::new("payments"))
+            .unwrap();
+
+        assert_eq!(subgraph.nodes.iter().count(), 2);
+        assert_eq!(subgraph.edges.values().count(), 1);
+        assert!(subgraph
+            .domain_identifier_map
+            .contains_key(&DomainIdentifier::new("payments")));
+        assert!(!subgraph
+            .domain_identifier_map
+            .contains_key(&DomainIdentifier::new("payouts")));
+        assert!(!subgraph
+            .value_map
+            .contains_key(&NodeValue::from(TestValue(3))));
+
+        let card = subgraph
+            .value_map
+            .get(&NodeValue::from(TestValue(1)))
+            .copied()
+            .unwrap();
+        assert_eq!(
+            subgraph.node_info.get(card).copied().flatten(),
+            Some("card")
+        );
+
+        assert!(matches!(
+            graph.subgraph_for_domain(DomainIdentifier::new("refunds")),
+            Err(GraphError::DomainNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_node_domains() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        builder.make_domain("payments", "Payment rules").unwrap();
+        builder.make_domain("payouts", "Payout rules").unwrap();
+        let card = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let three_ds = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        let bank = builder.make_value_node::<()>(TestValue(3).into(), None, None);
+        let wallet = builder.make_value_node::<()>(TestValue(4).into(), None, None);
+        builder
+            .make_edge(
+                card,
+                three_ds,
+                Strength::Strong,
+                Relation::Positive,
+                Some("payments"),
+            )
+            .unwrap();
+        builder
+            .make_edge(
+                three_ds,
+                bank,
+                Strength::Strong,
+                Relation::Negative,
+                Some("payouts"),
+            )
+            .unwrap();
+        builder
+            .make_edge(
+                wallet,
+                three_ds,
+                Strength::Normal,
+                Relation::Positive,
+                Some("payments"),
+            )
+            .unwrap();
+        builder
+            .make_edge(
+                wallet,
+                bank,
+                Strength::Normal,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .unwrap();
+        let graph = builder.build();
+
+        assert_eq!(
+            graph.node_domains(three_ds).unwrap(),
+            vec![
+                DomainIdentifier::new("payments"),
+                DomainIdentifier::new("payouts")
+            ]
+        );
+        assert_eq!(
+            graph.node_domains(wallet).unwrap(),
+            vec![DomainIdentifier::new("payments")]
+        );
+        assert!(matches!(
+            graph.node_domains(NodeId::with_id(10)),
+            Err(GraphError::NodeNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_satisfied_outputs() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let card = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let visa = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        let blocked = builder.make_value_node::<()>(TestValue(3).into(), None, None);
+        let all = builder
+            .make_all_aggregator::<()>(
+                &[
+                    (card, Relation::Positive, Strength::Strong),
+                    (visa, Relation::Positive, Strength::Strong),
+                    (blocked, Relation::Negative, Strength::Strong),
+                ],
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let any = builder
+            .make_any_aggregator::<()>(
+                &[
+                    (card, Relation::Positive, Strength::Normal),
+                    (visa, Relation::Positive, Strength::Normal),
+                ],
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let three_ds = builder.make_value_node::<()>(TestValue(4).into(), None, None);
+        let wallet = builder.make_value_node::<()>(TestValue(5).into(), None, None);
+        builder
+            .make_edge(
+                all,
+                three_ds,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .unwrap();
+        builder
+            .make_edge(
+                any,
+                wallet,
+                Strength::Normal,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .unwrap();
+        let graph = builder.build();
+
+        let known = |values: &[u8]| {
+            values
+                .iter()
+                .map(|value| NodeValue::Value(TestValue(*value)))
+                .collect::<FxHashSet<_>>()
+        };
+
+        // Output nodes are derived from their predecessors, so their own values need not be known
+        assert_eq!(
+            graph.satisfied_outputs(&known(&[1, 2])),
+            FxHashSet::from_iter([three_ds, wallet])
+        );
+        // A single unsatisfied predecessor fails the All aggregator, while one satisfied
+        // predecessor is enough for the Any aggregator
+        assert_eq!(
+            graph.satisfied_outputs(&known(&[1])),
+            FxHashSet::from_iter([wallet])
+        );
+        // A negative edge fails when its predecessor holds
+        assert_eq!(
+            graph.satisfied_outputs(&known(&[1, 2, 3])),
+            FxHashSet::from_iter([wallet])
+        );
+        assert!(graph.satisfied_outputs(&known(&[4, 5])).is_empty());
+    }
+
+    #[test]
+    fn test_satisfied_outputs_skips_removed_nodes() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let card = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let visa = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        builder
+            .make_edge(
+                card,
+                visa,
+                Strength::Strong,
+                Relation::Positive,
+                None::<DomainId>,
+            )
+            .unwrap();
+        builder
+            .apply_diff(crate::builder::GraphDiff {
+                remove_value_nodes: vec![TestValue(2).into()],
+                ..Default::default()
+            })
+            .unwrap();
+        let graph = builder.build();
+
+        let known = [1, 2]
+            .into_iter()
+            .map(|value| NodeValue::Value(TestValue(value)))
+            .collect::<FxHashSet<_>>();
+        assert_eq!(
+            graph.satisfied_outputs(&known),
+            FxHashSet::from_iter([card])
+        );
+    }
+
+    #[test]
+    fn test_satisfied_outputs_does_not_memoise_cycle_cuts() {
+        let mut builder = ConstraintGraphBuilder::<TestValue>::new();
+        let card = builder.make_value_node::<()>(TestValue(1).into(), None, None);
+        let visa = builder.make_value_node::<()>(TestValue(2).into(), None, None);
+        let credit = builder.make_value_node::<()>(TestValue(3).into(), None, None);
+        // The output of credit is evaluated first, entering the cycle through credit and cutting
+        // the edge back into it, which fails visa as its only predecessor is that edge
+        let credit_output = builder.make_value_node::<()>(TestValue(4).into(), None, None);
+        let visa_output = builder.make_value_node::<()>(TestValue(5).into(), None, None);
+        for (pred, succ, strength) in [
+            (card, credit, Strength::Normal),
+            (visa, credit, Strength::Normal),
+            (credit, visa, Strength::Strong),
+            (credit, credit_output, Strength::Normal),
+            (visa, visa_output, Strength::Normal),
+        ] {
+            builder
+                .make_edge(pred, succ, strength, Relation::Positive, None::<DomainId>)
+                .unwrap();
+        }
+        let graph = builder.build();
+
+        let known = [1, 2, 3]
+            .into_iter()
+            .map(|value| NodeValue::Value(TestValue(value)))
+            .collect::<FxHashSet<_>>();
+
+        assert_eq!(
+            graph.satisfied_outputs(&known),
+            FxHashSet::from_iter([credit_output, visa_output])
+        );
+    }
+}
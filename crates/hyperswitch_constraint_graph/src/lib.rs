@@ -5,8 +5,8 @@ pub mod graph;
 pub mod types;
 
 pub use builder::ConstraintGraphBuilder;
-pub use error::{AnalysisTrace, GraphError};
-pub use graph::ConstraintGraph;
+pub use error::{AnalysisTrace, FailedNode, GraphError};
+pub use graph::{ConstraintGraph, NodeEvaluation};
 #[cfg(feature = "viz")]
 pub use types::NodeViz;
 pub use types::{
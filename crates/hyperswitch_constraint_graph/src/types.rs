@@ -33,6 +33,12 @@ pub struct Node<V: ValueNode> {
     pub node_type: NodeType<V>,
     pub preds: Vec<EdgeId>,
     pub succs: Vec<EdgeId>,
+    /// Feature flag the node is gated behind. While the flag is disabled in the checking context,
+    /// the node is treated as absent.
+    pub feature_flag: Option<String>,
+    /// Set when the node is removed by a graph diff. Removed nodes keep their id, detached from
+    /// the rest of the graph, and are skipped when iterating over the nodes of the graph.
+    pub removed: bool,
 }
 
 impl<V: ValueNode> Node<V> {
@@ -41,6 +47,8 @@ impl<V: ValueNode> Node<V> {
             node_type,
             preds: Vec::new(),
             succs: Vec::new(),
+            feature_flag: None,
+            removed: false,
         }
     }
 }
@@ -132,6 +140,9 @@ pub struct Edge {
     pub pred: NodeId,
     pub succ: NodeId,
     pub domain: Option<DomainId>,
+    /// Set when the edge is removed by a graph diff. Removed edges keep their id, detached from
+    /// their nodes, and are skipped when iterating over the edges of the graph.
+    pub removed: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -185,6 +196,12 @@ pub trait CheckingContext {
         &self,
         expected: &<Self::Value as ValueNode>::Key,
     ) -> Option<Vec<Self::Value>>;
+
+    /// Whether nodes gated behind the feature flag are active. Gated nodes are inactive unless
+    /// the context enables their flag.
+    fn is_feature_enabled(&self, _feature_flag: &str) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -248,7 +265,32 @@ impl DerefMut for CycleCheck {
     }
 }
 
-pub trait Metadata: erased_serde::Serialize + Any + Send + Sync + fmt::Debug {}
+/// Metadata attached to graph nodes. Types opt in with an `impl Metadata` of their own, rather
+/// than through a blanket implementation, so that they can provide a redacted form.
+pub trait Metadata: erased_serde::Serialize + Any + Send + Sync + fmt::Debug {
+    /// Form of the metadata which is safe to persist outside the process, such as when caching a
+    /// serialized graph. Metadata without a redacted form is skipped entirely.
+    fn redacted_serialize(&self) -> Option<&dyn erased_serde::Serialize> {
+        None
+    }
+}
 erased_serde::serialize_trait_object!(Metadata);
 
-impl<M> Metadata for M where M: erased_serde::Serialize + Any + Send + Sync + fmt::Debug {}
+impl Metadata for () {}
+
+/// Serializes node metadata through its redacted form, for use with `#[serde(serialize_with)]`
+/// on every serialized metadata field, such as those of analysis traces
+pub fn serialize_redacted_metadata<S>(
+    metadata: &Option<Arc<dyn Metadata>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(
+        &metadata
+            .as_ref()
+            .and_then(|metadata| metadata.redacted_serialize()),
+        serializer,
+    )
+}
@@ -360,6 +360,8 @@ pub enum SyncRequestType {
 #[derive(Debug, Default, Clone)]
 pub struct PaymentsCancelData {
     pub amount: Option<i64>,
+    /// Amount to be reversed when only a part of the authorization is voided
+    pub reversal_amount: Option<i64>,
     pub currency: Option<storage_enums::Currency>,
     pub connector_transaction_id: String,
     pub cancellation_reason: Option<String>,
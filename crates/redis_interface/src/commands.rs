@@ -15,14 +15,16 @@ use common_utils::{
 };
 use error_stack::{report, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, SetsInterface, StreamsInterface},
+    interfaces::{
+        ClientLike, HashesInterface, KeysInterface, LuaInterface, SetsInterface, StreamsInterface,
+    },
     prelude::RedisErrorKind,
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
         MultipleValues, RedisKey, RedisMap, RedisValue, Scanner, SetOptions, XCap, XReadResponse,
     },
 };
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
 use tracing::instrument;
 
 use crate::{
@@ -71,6 +73,30 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    /// Overwrites the value of an existing key, keeping its remaining time to live.
+    /// Replies with `KeyNotSet` if the key does not exist.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_key_if_exists_without_modifying_ttl<V>(
+        &self,
+        key: &str,
+        value: V,
+    ) -> CustomResult<SetnxReply, errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        self.pool
+            .set(
+                self.add_prefix(key),
+                value,
+                Some(Expiration::KEEPTTL),
+                Some(SetOptions::XX),
+                false,
+            )
+            .await
+            .change_context(errors::RedisError::SetFailed)
+    }
+
     pub async fn set_multiple_keys_if_not_exist<V>(
         &self,
         value: V,
@@ -209,6 +235,16 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::DeleteFailed)
     }
 
+    /// Deletes the keys concurrently, so that the commands are pipelined over the connection when
+    /// auto pipelining is enabled. The results are in the order of the keys.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn delete_multiple_keys(
+        &self,
+        keys: &[String],
+    ) -> Vec<CustomResult<DelReply, errors::RedisError>> {
+        futures::future::join_all(keys.iter().map(|key| self.delete_key(key))).await
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key_with_expiry<V>(
         &self,
@@ -269,6 +305,16 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetExpiryFailed)
     }
 
+    /// Returns the remaining time to live of the key in seconds.
+    /// Redis replies with `-2` if the key does not exist and `-1` if it has no expiry.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_ttl(&self, key: &str) -> CustomResult<i64, errors::RedisError> {
+        self.pool
+            .ttl(self.add_prefix(key))
+            .await
+            .change_context(errors::RedisError::GetFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_expire_at(
         &self,
@@ -281,6 +327,29 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetExpiryFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self, lua_script, values))]
+    pub async fn evaluate_redis_script<V, T>(
+        &self,
+        lua_script: &'static str,
+        keys: Vec<String>,
+        values: V,
+    ) -> CustomResult<T, errors::RedisError>
+    where
+        V: TryInto<MultipleValues> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+        T: FromRedis + Send + 'static,
+    {
+        let keys = keys
+            .iter()
+            .map(|key| self.add_prefix(key))
+            .collect::<Vec<_>>();
+
+        self.pool
+            .eval(lua_script, keys, values)
+            .await
+            .change_context(errors::RedisError::ScriptExecutionFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_hash_fields<V>(
         &self,
@@ -402,6 +471,39 @@ impl super::RedisConnectionPool {
             .await)
     }
 
+    /// Scans the keys matching the pattern, returning them without the key prefix of the pool so
+    /// that they can be passed to the other commands. In cluster mode the keys of every primary
+    /// node are scanned. The scan stops at the first error, which is returned.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn scan_keys(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        let key_prefix = self.add_prefix("");
+        let client = self.pool.next();
+        let scan_results = if client.is_clustered() {
+            client
+                .scan_cluster(self.add_prefix(pattern), count, None)
+                .left_stream()
+        } else {
+            client
+                .scan(self.add_prefix(pattern), count, None)
+                .right_stream()
+        };
+        let keys = scan_results
+            .map(|value| value.map(|mut v| v.take_results().unwrap_or_default()))
+            .try_concat()
+            .await
+            .change_context(errors::RedisError::ScanFailed)?;
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| key.as_str())
+            .map(|key| key.strip_prefix(&key_prefix).unwrap_or(key).to_string())
+            .collect())
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn hscan_and_deserialize<T>(
         &self,
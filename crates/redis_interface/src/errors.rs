@@ -68,4 +68,8 @@ pub enum RedisError {
     OnMessageError,
     #[error("Got an unknown result from redis")]
     UnknownResult,
+    #[error("Failed to execute the lua script in Redis")]
+    ScriptExecutionFailed,
+    #[error("Failed to scan keys in Redis")]
+    ScanFailed,
 }
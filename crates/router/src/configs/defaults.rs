@@ -69,6 +69,13 @@ impl Default for super::settings::Locker {
             locker_enabled: true,
             //Time to live for storage entries in locker
             ttl_for_storage_in_secs: 60 * 60 * 24 * 365 * 7,
+            temp_locker_encryption_algorithm: Default::default(),
+            delete_tokenized_data_task_tag: "BASILISK-V3".into(),
+            temp_locker_split_values: false,
+            tokenized_data_redaction_policy: Default::default(),
+            max_in_flight_operations: 10_000,
+            //Time after creation beyond which temporary locker tokens are not extended
+            temp_locker_max_lifetime_in_secs: 60 * 60,
         }
     }
 }
@@ -131,6 +138,15 @@ impl Default for super::settings::KvConfig {
     }
 }
 
+#[cfg(feature = "olap")]
+impl Default for super::settings::MerchantKeyStoreConfig {
+    fn default() -> Self {
+        Self {
+            decryption_concurrency: 32,
+        }
+    }
+}
+
 use super::settings::{
     Mandates, SupportedConnectorsForMandate, SupportedPaymentMethodTypesForMandate,
     SupportedPaymentMethodsForMandate,
@@ -37,17 +37,20 @@ impl SecretsHandler for settings::Jwekey {
             rust_locker_encryption_key,
             vault_private_key,
             tunnel_private_key,
+            temp_locker_value1_encryption_key,
         ) = tokio::try_join!(
             secret_management_client.get_secret(jwekey.vault_encryption_key.clone()),
             secret_management_client.get_secret(jwekey.rust_locker_encryption_key.clone()),
             secret_management_client.get_secret(jwekey.vault_private_key.clone()),
-            secret_management_client.get_secret(jwekey.tunnel_private_key.clone())
+            secret_management_client.get_secret(jwekey.tunnel_private_key.clone()),
+            secret_management_client.get_secret(jwekey.temp_locker_value1_encryption_key.clone())
         )?;
         Ok(value.transition_state(|_| Self {
             vault_encryption_key,
             rust_locker_encryption_key,
             vault_private_key,
             tunnel_private_key,
+            temp_locker_value1_encryption_key,
         }))
     }
 }
@@ -357,6 +360,8 @@ pub(crate) async fn fetch_raw_secrets(
         opensearch: conf.opensearch,
         #[cfg(feature = "kv_store")]
         kv_config: conf.kv_config,
+        #[cfg(feature = "olap")]
+        merchant_key_store: conf.merchant_key_store,
         #[cfg(feature = "frm")]
         frm: conf.frm,
         #[cfg(feature = "olap")]
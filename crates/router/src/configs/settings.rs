@@ -110,6 +110,8 @@ pub struct Settings<S: SecretState> {
     pub analytics: SecretStateContainer<AnalyticsConfig, S>,
     #[cfg(feature = "kv_store")]
     pub kv_config: KvConfig,
+    #[cfg(feature = "olap")]
+    pub merchant_key_store: MerchantKeyStoreConfig,
     #[cfg(feature = "frm")]
     pub frm: Frm,
     #[cfg(feature = "olap")]
@@ -196,6 +198,13 @@ pub struct KvConfig {
     pub soft_kill: Option<bool>,
 }
 
+#[cfg(feature = "olap")]
+#[derive(Debug, Deserialize, Clone)]
+pub struct MerchantKeyStoreConfig {
+    /// Maximum number of merchant key stores decrypted concurrently when listing them
+    pub decryption_concurrency: usize,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct PaymentLink {
     pub sdk_url: String,
@@ -464,6 +473,22 @@ pub struct Locker {
     pub locker_signing_key_id: String,
     pub locker_enabled: bool,
     pub ttl_for_storage_in_secs: i64,
+    pub temp_locker_encryption_algorithm: TempLockerEncryptionAlgorithm,
+    pub delete_tokenized_data_task_tag: String,
+    pub temp_locker_split_values: bool,
+    pub tokenized_data_redaction_policy: api_models::payment_methods::TokenizedDataRedactionPolicy,
+    pub max_in_flight_operations: usize,
+    pub temp_locker_max_lifetime_in_secs: i64,
+}
+
+/// Algorithm used to encrypt payloads stored in the temporary locker
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum TempLockerEncryptionAlgorithm {
+    #[default]
+    GcmAes256,
+    #[serde(rename = "chacha20_poly1305")]
+    ChaCha20Poly1305,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -486,6 +511,7 @@ pub struct Jwekey {
     pub rust_locker_encryption_key: Secret<String>,
     pub vault_private_key: Secret<String>,
     pub tunnel_private_key: Secret<String>,
+    pub temp_locker_value1_encryption_key: Secret<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
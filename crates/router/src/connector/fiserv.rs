@@ -3,7 +3,10 @@ pub mod transformers;
 use std::fmt::Debug;
 
 use base64::Engine;
-use common_utils::request::RequestContent;
+use common_utils::{
+    crypto::{self, VerifySignature},
+    request::RequestContent,
+};
 use diesel_models::enums;
 use error_stack::{report, ResultExt};
 use masking::{ExposeInterface, PeekInterface};
@@ -28,6 +31,7 @@ use crate::{
     types::{
         self,
         api::{self, ConnectorCommon, ConnectorCommonExt},
+        domain,
     },
     utils::BytesExt,
 };
@@ -50,11 +54,22 @@ impl Fiserv {
         } = auth;
         let raw_signature = format!("{}{request_id}{timestamp}{payload}", api_key.peek());
 
-        let key = hmac::Key::new(hmac::HMAC_SHA256, api_secret.expose().as_bytes());
+        let key = hmac::Key::new(
+            hmac::HMAC_SHA256,
+            api_secret.into_inner().expose().as_bytes(),
+        );
         let signature_value =
             consts::BASE64_ENGINE.encode(hmac::sign(&key, raw_signature.as_bytes()).as_ref());
         Ok(signature_value)
     }
+
+    /// Whether a flow can be retried after the request to Fiserv timed out.
+    /// The `Client-Request-Id` header is generated afresh for every request, so Fiserv cannot
+    /// deduplicate retries and flows that move funds are never retried.
+    pub fn is_retry_safe_on_timeout(&self, flow: services::ConnectorFlow) -> bool {
+        self.get_connector_specifications()
+            .is_some_and(|specifications| specifications.is_retry_safe(flow, false))
+    }
 }
 
 impl<Flow, Request, Response> ConnectorCommonExt<Flow, Request, Response> for Fiserv
@@ -183,6 +198,54 @@ impl ConnectorValidation for Fiserv {
             ),
         }
     }
+
+    fn get_connector_specifications(&self) -> Option<services::ConnectorSpecifications> {
+        Some(services::ConnectorSpecifications {
+            supported_payment_methods: vec![(
+                enums::PaymentMethod::Card,
+                vec![
+                    enums::PaymentMethodType::Credit,
+                    enums::PaymentMethodType::Debit,
+                ],
+            )],
+            supported_currencies: None,
+            supported_capture_methods: vec![
+                enums::CaptureMethod::Automatic,
+                enums::CaptureMethod::Manual,
+            ],
+            supported_flows: vec![
+                services::ConnectorFlow::Authorize,
+                services::ConnectorFlow::Capture,
+                services::ConnectorFlow::Void,
+                services::ConnectorFlow::PSync,
+                services::ConnectorFlow::Refund,
+                services::ConnectorFlow::RSync,
+            ],
+            flow_retry_safety: vec![
+                // A repeated authorize, capture or refund could move funds twice
+                (
+                    services::ConnectorFlow::Authorize,
+                    services::RetrySafety::RequiresIdempotencyKey,
+                ),
+                (
+                    services::ConnectorFlow::Capture,
+                    services::RetrySafety::RequiresIdempotencyKey,
+                ),
+                (
+                    services::ConnectorFlow::Refund,
+                    services::RetrySafety::RequiresIdempotencyKey,
+                ),
+                // A repeated partial reversal would reverse the amount twice
+                (
+                    services::ConnectorFlow::Void,
+                    services::RetrySafety::RequiresIdempotencyKey,
+                ),
+                (services::ConnectorFlow::PSync, services::RetrySafety::Safe),
+                (services::ConnectorFlow::RSync, services::RetrySafety::Safe),
+            ],
+            supports_partial_void: true,
+        })
+    }
 }
 
 impl api::ConnectorAccessToken for Fiserv {}
@@ -298,9 +361,9 @@ impl ConnectorIntegration<api::Void, types::PaymentsCancelData, types::PaymentsR
         event_builder: Option<&mut ConnectorEvent>,
         res: types::Response,
     ) -> CustomResult<types::PaymentsCancelRouterData, errors::ConnectorError> {
-        let response: fiserv::FiservPaymentsResponse = res
+        let response: fiserv::FiservCancelResponse = res
             .response
-            .parse_struct("Fiserv PaymentResponse")
+            .parse_struct("Fiserv CancelResponse")
             .change_context(errors::ConnectorError::ResponseDeserializationFailed)?;
         event_builder.map(|i| i.set_response_body(&response));
         router_env::logger::info!(connector_response=?response);
@@ -777,8 +840,56 @@ impl ConnectorIntegration<api::RSync, types::RefundsData, types::RefundsResponse
     }
 }
 
+/// Checks the base64 encoded HMAC-SHA256 signature Fiserv sends in the authorization header
+/// against the raw webhook body, signed with the merchant's webhook secret
+fn verify_fiserv_webhook_signature(
+    request: &api::IncomingWebhookRequestDetails<'_>,
+    webhook_secret: &[u8],
+) -> CustomResult<(), errors::ConnectorError> {
+    let signature = connector_utils::get_header_key_value(headers::AUTHORIZATION, request.headers)
+        .change_context(errors::ConnectorError::WebhookSignatureNotFound)?;
+    let signature = consts::BASE64_ENGINE
+        .decode(signature)
+        .change_context(errors::ConnectorError::WebhookSignatureNotFound)?;
+
+    // ring verifies the tag in constant time, the signature must never be compared with `==`
+    let is_verified = crypto::HmacSha256
+        .verify_signature(webhook_secret, &signature, request.body)
+        .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
+
+    if is_verified {
+        Ok(())
+    } else {
+        Err(report!(
+            errors::ConnectorError::WebhookSourceVerificationFailed
+        ))
+    }
+}
+
 #[async_trait::async_trait]
 impl api::IncomingWebhook for Fiserv {
+    async fn verify_webhook_source(
+        &self,
+        request: &api::IncomingWebhookRequestDetails<'_>,
+        merchant_account: &domain::MerchantAccount,
+        merchant_connector_account: domain::MerchantConnectorAccount,
+        connector_label: &str,
+    ) -> CustomResult<bool, errors::ConnectorError> {
+        // The webhook secret is configured in the connector webhook details and is distinct from
+        // the api secret used to sign requests
+        let connector_webhook_secrets = self
+            .get_webhook_source_verification_merchant_secret(
+                merchant_account,
+                connector_label,
+                merchant_connector_account,
+            )
+            .await
+            .change_context(errors::ConnectorError::WebhookSourceVerificationFailed)?;
+
+        verify_fiserv_webhook_signature(request, &connector_webhook_secrets.secret)?;
+        Ok(true)
+    }
+
     fn get_webhook_object_reference_id(
         &self,
         _request: &api::IncomingWebhookRequestDetails<'_>,
@@ -800,3 +911,65 @@ impl api::IncomingWebhook for Fiserv {
         Err(report!(errors::ConnectorError::WebhooksNotImplemented))
     }
 }
+
+#[cfg(test)]
+mod webhook_tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+
+    use super::*;
+
+    const WEBHOOK_SECRET: &[u8] = b"fiserv_webhook_secret";
+    const WEBHOOK_BODY: &[u8] = br#"{"gatewayResponse":{"transactionState":"CAPTURED"}}"#;
+
+    fn build_headers(signature: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            HeaderName::from_static("authorization"),
+            HeaderValue::from_static(signature),
+        );
+        headers
+    }
+
+    fn build_request<'a>(headers: &'a HeaderMap) -> api::IncomingWebhookRequestDetails<'a> {
+        api::IncomingWebhookRequestDetails {
+            method: actix_web::http::Method::POST,
+            uri: "/webhooks".parse().unwrap(),
+            headers,
+            body: WEBHOOK_BODY,
+            query_params: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_verify_webhook_signature_with_known_good_signature() {
+        let headers = build_headers("jVz8P/XS2z6Jl1Q6LkYKt0eLmkdJcKUjKAvBkZdUtAY=");
+        let request = build_request(&headers);
+
+        assert!(verify_fiserv_webhook_signature(&request, WEBHOOK_SECRET).is_ok());
+    }
+
+    #[test]
+    fn test_verify_webhook_signature_rejects_mismatch() {
+        let headers = build_headers("jVz8P/XS2z6Jl1Q6LkYKt0eLmkdJcKUjKAvBkZdUtAY=");
+        let request = build_request(&headers);
+
+        let result = verify_fiserv_webhook_signature(&request, b"api_secret");
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ConnectorError::WebhookSourceVerificationFailed
+        ));
+    }
+
+    #[test]
+    fn test_verify_webhook_signature_rejects_tampered_signature() {
+        let headers = build_headers("kVz8P/XS2z6Jl1Q6LkYKt0eLmkdJcKUjKAvBkZdUtAY=");
+        let request = build_request(&headers);
+
+        let result = verify_fiserv_webhook_signature(&request, WEBHOOK_SECRET);
+        assert!(matches!(
+            result.unwrap_err().current_context(),
+            errors::ConnectorError::WebhookSourceVerificationFailed
+        ));
+    }
+}
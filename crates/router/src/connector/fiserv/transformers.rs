@@ -4,11 +4,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     connector::utils::{
-        self, CardData as CardDataUtil, PaymentsCancelRequestData, PaymentsSyncRequestData,
-        RouterData,
+        self, CardData as CardDataUtil, PaymentsAuthorizeRequestData, PaymentsCancelRequestData,
+        PaymentsSyncRequestData, RouterData,
     },
     core::errors,
-    pii::Secret,
+    pii::{PeekInterface, Secret},
     types::{self, api, domain, storage::enums},
 };
 
@@ -29,7 +29,10 @@ impl<T> TryFrom<(&api::CurrencyUnit, enums::Currency, i64, T)> for FiservRouterD
             T,
         ),
     ) -> Result<Self, Self::Error> {
-        let amount = utils::get_amount_as_string(currency_unit, amount, currency)?;
+        let amount = match currency_unit {
+            api::CurrencyUnit::Base => get_fiserv_amount(amount, currency)?,
+            api::CurrencyUnit::Minor => amount.to_string(),
+        };
         Ok(Self {
             amount,
             router_data,
@@ -37,10 +40,36 @@ impl<T> TryFrom<(&api::CurrencyUnit, enums::Currency, i64, T)> for FiservRouterD
     }
 }
 
+/// Converts an amount in minor units to the major units Fiserv expects, with exactly as many
+/// decimals as the currency has, so 12345 USD cents are sent as "123.45" and 12345 JPY as "12345"
+fn get_fiserv_amount(
+    amount: i64,
+    currency: enums::Currency,
+) -> Result<String, error_stack::Report<errors::ConnectorError>> {
+    let amount = u64::try_from(amount)
+        .change_context(errors::ConnectorError::ParsingFailed)
+        .attach_printable("Fiserv amounts must not be negative")?;
+    let decimal_places = currency.number_of_digits_after_decimal_point();
+    let minor_units_per_major_unit = 10_u64.pow(u32::from(decimal_places));
+
+    Ok(if decimal_places == 0 {
+        amount.to_string()
+    } else {
+        format!(
+            "{}.{:0width$}",
+            amount / minor_units_per_major_unit,
+            amount % minor_units_per_major_unit,
+            width = usize::from(decimal_places)
+        )
+    })
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FiservPaymentsRequest {
     amount: Amount,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount_details: Option<AmountDetails>,
     source: Source,
     transaction_details: TransactionDetails,
     merchant_details: MerchantDetails,
@@ -85,6 +114,41 @@ pub struct Amount {
     currency: String,
 }
 
+/// Breakdown of the total amount, sent for surcharged payments
+#[derive(Debug, Serialize)]
+pub struct AmountDetails {
+    #[serde(serialize_with = "utils::str_to_f32")]
+    subtotal: String,
+    #[serde(serialize_with = "utils::str_to_f32")]
+    tax: String,
+    #[serde(serialize_with = "utils::str_to_f32")]
+    surcharge: String,
+}
+
+fn get_amount_details(
+    item: &types::PaymentsAuthorizeRouterData,
+) -> Result<Option<AmountDetails>, error_stack::Report<errors::ConnectorError>> {
+    let request = &item.request;
+    let (Some(surcharge), Some(tax)) = (
+        request.get_surcharge_amount(),
+        request.get_tax_on_surcharge_amount(),
+    ) else {
+        return Ok(None);
+    };
+    let subtotal = request.get_original_amount();
+
+    if subtotal + surcharge + tax != request.amount {
+        return Err(errors::ConnectorError::RequestEncodingFailed)
+            .attach_printable("Surcharge breakdown does not add up to the amount being charged");
+    }
+
+    Ok(Some(AmountDetails {
+        subtotal: get_fiserv_amount(subtotal, request.currency)?,
+        tax: get_fiserv_amount(tax, request.currency)?,
+        surcharge: get_fiserv_amount(surcharge, request.currency)?,
+    }))
+}
+
 #[derive(Default, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionDetails {
@@ -194,8 +258,10 @@ impl TryFrom<&FiservRouterData<&types::PaymentsAuthorizeRouterData>> for FiservP
                 ))
             }?,
         };
+        let amount_details = get_amount_details(item.router_data)?;
         Ok(Self {
             amount,
+            amount_details,
             source,
             transaction_details,
             merchant_details,
@@ -204,10 +270,44 @@ impl TryFrom<&FiservRouterData<&types::PaymentsAuthorizeRouterData>> for FiservP
     }
 }
 
+/// Shortest api secret accepted, since a shorter key makes the request signatures easy to forge
+const FISERV_API_SECRET_MIN_LENGTH: usize = 8;
+
+/// Api secret of the merchant, checked to be non-blank and of at least
+/// `FISERV_API_SECRET_MIN_LENGTH` characters as it keys the request signatures
+pub struct FiservApiSecret(Secret<String>);
+
+impl FiservApiSecret {
+    pub(super) fn into_inner(self) -> Secret<String> {
+        self.0
+    }
+}
+
+impl TryFrom<&Secret<String>> for FiservApiSecret {
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(api_secret: &Secret<String>) -> Result<Self, Self::Error> {
+        if api_secret.peek().trim().is_empty() {
+            Err(errors::ConnectorError::InvalidConnectorConfig {
+                config: "api_secret",
+            })
+            .attach_printable("Fiserv api secret must not be blank")
+        } else if api_secret.peek().chars().count() < FISERV_API_SECRET_MIN_LENGTH {
+            Err(errors::ConnectorError::InvalidConnectorConfig {
+                config: "api_secret",
+            })
+            .attach_printable(format!(
+                "Fiserv api secret must be at least {FISERV_API_SECRET_MIN_LENGTH} characters long"
+            ))
+        } else {
+            Ok(Self(api_secret.to_owned()))
+        }
+    }
+}
+
 pub struct FiservAuthType {
     pub(super) api_key: Secret<String>,
     pub(super) merchant_account: Secret<String>,
-    pub(super) api_secret: Secret<String>,
+    pub(super) api_secret: FiservApiSecret,
 }
 
 impl TryFrom<&types::ConnectorAuthType> for FiservAuthType {
@@ -222,7 +322,7 @@ impl TryFrom<&types::ConnectorAuthType> for FiservAuthType {
             Ok(Self {
                 api_key: api_key.to_owned(),
                 merchant_account: key1.to_owned(),
-                api_secret: api_secret.to_owned(),
+                api_secret: FiservApiSecret::try_from(api_secret)?,
             })
         } else {
             Err(errors::ConnectorError::FailedToObtainAuthType)?
@@ -233,6 +333,9 @@ impl TryFrom<&types::ConnectorAuthType> for FiservAuthType {
 #[derive(Default, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FiservCancelRequest {
+    /// Amount to reverse, sent only for a partial reversal of the authorization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<Amount>,
     transaction_details: TransactionDetails,
     merchant_details: MerchantDetails,
     reference_transaction_details: ReferenceTransactionDetails,
@@ -248,7 +351,18 @@ impl TryFrom<&types::PaymentsCancelRouterData> for FiservCancelRequest {
             .change_context(errors::ConnectorError::InvalidConnectorConfig {
                 config: "Merchant connector account metadata",
             })?;
+        let amount = match get_partial_reversal_amount(&item.request)? {
+            Some(reversal_amount) => {
+                let currency = item.request.get_currency()?;
+                Some(Amount {
+                    total: get_fiserv_amount(reversal_amount, currency)?,
+                    currency: currency.to_string(),
+                })
+            }
+            None => None,
+        };
         Ok(Self {
+            amount,
             merchant_details: MerchantDetails {
                 merchant_id: auth.merchant_account,
                 terminal_id: Some(session.terminal_id),
@@ -265,6 +379,26 @@ impl TryFrom<&types::PaymentsCancelRouterData> for FiservCancelRequest {
     }
 }
 
+/// Amount to reverse when only a part of the authorization is voided, reversing the whole
+/// authorized amount is a full void
+fn get_partial_reversal_amount(
+    request: &types::PaymentsCancelData,
+) -> Result<Option<i64>, error_stack::Report<errors::ConnectorError>> {
+    let Some(reversal_amount) = request.reversal_amount else {
+        return Ok(None);
+    };
+    let amount = request.get_amount()?;
+
+    if reversal_amount <= 0 || reversal_amount > amount {
+        return Err(errors::ConnectorError::InvalidDataFormat {
+            field_name: "reversal_amount",
+        })
+        .attach_printable("Reversal amount must be positive and not exceed the authorized amount");
+    }
+
+    Ok(Some(reversal_amount).filter(|reversal_amount| *reversal_amount < amount))
+}
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
@@ -323,6 +457,7 @@ impl From<FiservPaymentStatus> for enums::RefundStatus {
 #[serde(rename_all = "camelCase")]
 pub struct FiservPaymentsResponse {
     gateway_response: GatewayResponse,
+    processor_response_details: Option<ProcessorResponseDetails>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -332,6 +467,12 @@ pub struct FiservSyncResponse {
     sync_responses: Vec<FiservPaymentsResponse>,
 }
 
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct FiservCancelResponse {
+    payment_response: FiservPaymentsResponse,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct GatewayResponse {
@@ -347,7 +488,137 @@ pub struct TransactionProcessingDetails {
     transaction_id: String,
 }
 
-impl<F, T>
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessorResponseDetails {
+    approval_code: Option<String>,
+    reference_number: Option<String>,
+    response_code: Option<String>,
+}
+
+/// Reason of a decline, as told by the processor response code of the transaction
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FiservDeclineReason {
+    DoNotHonor,
+    InsufficientFunds,
+    ExpiredCard,
+    InvalidCardNumber,
+    LostOrStolenCard,
+    IncorrectCvc,
+    ExceedsLimit,
+    SuspectedFraud,
+    ProcessingError,
+}
+
+impl FiservDeclineReason {
+    fn from_processor_response_code(response_code: &str) -> Option<Self> {
+        match response_code {
+            "05" => Some(Self::DoNotHonor),
+            "51" => Some(Self::InsufficientFunds),
+            "54" => Some(Self::ExpiredCard),
+            "14" => Some(Self::InvalidCardNumber),
+            "41" | "43" => Some(Self::LostOrStolenCard),
+            "82" | "N7" => Some(Self::IncorrectCvc),
+            "61" | "65" => Some(Self::ExceedsLimit),
+            "59" => Some(Self::SuspectedFraud),
+            "91" | "96" => Some(Self::ProcessingError),
+            _ => None,
+        }
+    }
+
+    /// Declines by the issuer fail the operation of the flow, while processing errors fail the
+    /// attempt
+    fn attempt_status(self, issuer_decline_status: enums::AttemptStatus) -> enums::AttemptStatus {
+        match self {
+            Self::DoNotHonor
+            | Self::InsufficientFunds
+            | Self::ExpiredCard
+            | Self::InvalidCardNumber
+            | Self::LostOrStolenCard
+            | Self::IncorrectCvc
+            | Self::ExceedsLimit
+            | Self::SuspectedFraud => issuer_decline_status,
+            Self::ProcessingError => enums::AttemptStatus::Failure,
+        }
+    }
+}
+
+/// Status of an attempt of which the transaction was declined by the issuer, which depends on the
+/// flow the transaction was made in
+pub trait FiservIssuerDeclineStatus {
+    fn issuer_decline_status() -> enums::AttemptStatus;
+}
+
+impl FiservIssuerDeclineStatus for types::PaymentsAuthorizeData {
+    fn issuer_decline_status() -> enums::AttemptStatus {
+        enums::AttemptStatus::AuthorizationFailed
+    }
+}
+
+impl FiservIssuerDeclineStatus for types::PaymentsCaptureData {
+    fn issuer_decline_status() -> enums::AttemptStatus {
+        enums::AttemptStatus::CaptureFailed
+    }
+}
+
+impl FiservIssuerDeclineStatus for types::PaymentsCancelData {
+    fn issuer_decline_status() -> enums::AttemptStatus {
+        enums::AttemptStatus::VoidFailed
+    }
+}
+
+fn get_decline_reason(
+    processor_response_details: Option<&ProcessorResponseDetails>,
+) -> Option<FiservDeclineReason> {
+    processor_response_details
+        .and_then(|details| details.response_code.as_deref())
+        .and_then(FiservDeclineReason::from_processor_response_code)
+}
+
+/// Status of the attempt, telling declines by the issuer apart from processing errors through the
+/// processor response code when Fiserv declines the transaction
+fn get_attempt_status(
+    transaction_state: FiservPaymentStatus,
+    processor_response_details: Option<&ProcessorResponseDetails>,
+    issuer_decline_status: enums::AttemptStatus,
+) -> enums::AttemptStatus {
+    match transaction_state {
+        FiservPaymentStatus::Declined | FiservPaymentStatus::Failed => {
+            get_decline_reason(processor_response_details)
+                .map_or(enums::AttemptStatus::Failure, |decline_reason| {
+                    decline_reason.attempt_status(issuer_decline_status)
+                })
+        }
+        transaction_state => enums::AttemptStatus::from(transaction_state),
+    }
+}
+
+/// Approval code, retrieval reference number and processor response code of the transaction,
+/// which merchants need on receipts and for dispute representment, along with the decline reason
+/// when the processor response code is a known decline code
+fn get_processor_response_metadata(
+    processor_response_details: Option<&ProcessorResponseDetails>,
+) -> Option<serde_json::Value> {
+    processor_response_details.map(|details| {
+        let mut metadata = serde_json::json!({
+            "approval_code": details.approval_code,
+            "retrieval_reference_number": details.reference_number,
+            "processor_response_code": details.response_code,
+        });
+        if let (Some(decline_reason), Some(metadata)) =
+            (get_decline_reason(Some(details)), metadata.as_object_mut())
+        {
+            metadata.insert(
+                "decline_reason".to_string(),
+                serde_json::json!(decline_reason),
+            );
+        }
+        metadata
+    })
+}
+
+impl<F, T: FiservIssuerDeclineStatus>
     TryFrom<types::ResponseRouterData<F, FiservPaymentsResponse, T, types::PaymentsResponseData>>
     for types::RouterData<F, T, types::PaymentsResponseData>
 {
@@ -356,16 +627,22 @@ impl<F, T>
         item: types::ResponseRouterData<F, FiservPaymentsResponse, T, types::PaymentsResponseData>,
     ) -> Result<Self, Self::Error> {
         let gateway_resp = item.response.gateway_response;
+        let processor_response_details = item.response.processor_response_details.as_ref();
+        let connector_metadata = get_processor_response_metadata(processor_response_details);
 
         Ok(Self {
-            status: enums::AttemptStatus::from(gateway_resp.transaction_state),
+            status: get_attempt_status(
+                gateway_resp.transaction_state,
+                processor_response_details,
+                T::issuer_decline_status(),
+            ),
             response: Ok(types::PaymentsResponseData::TransactionResponse {
                 resource_id: types::ResponseId::ConnectorTransactionId(
                     gateway_resp.transaction_processing_details.transaction_id,
                 ),
                 redirection_data: None,
                 mandate_reference: None,
-                connector_metadata: None,
+                connector_metadata,
                 network_txn_id: None,
                 connector_response_reference_id: Some(
                     gateway_resp.transaction_processing_details.order_id,
@@ -378,6 +655,56 @@ impl<F, T>
     }
 }
 
+impl TryFrom<types::PaymentsCancelResponseRouterData<FiservCancelResponse>>
+    for types::PaymentsCancelRouterData
+{
+    type Error = error_stack::Report<errors::ConnectorError>;
+    fn try_from(
+        item: types::PaymentsCancelResponseRouterData<FiservCancelResponse>,
+    ) -> Result<Self, Self::Error> {
+        let partial_reversal_amount = get_partial_reversal_amount(&item.data.request)?;
+        let router_data = Self::try_from(types::ResponseRouterData {
+            response: item.response.payment_response,
+            data: item.data,
+            http_code: item.http_code,
+        })?;
+        let Some(reversal_amount) = partial_reversal_amount else {
+            return Ok(router_data);
+        };
+
+        // A partially reversed authorization stays authorized for the remaining amount
+        let remaining_authorization_amount = router_data.request.get_amount()? - reversal_amount;
+        let status = match router_data.status {
+            enums::AttemptStatus::Voided => enums::AttemptStatus::Authorized,
+            status => status,
+        };
+        let response = router_data.response.map(|mut response| {
+            if let types::PaymentsResponseData::TransactionResponse {
+                connector_metadata, ..
+            } = &mut response
+            {
+                let mut metadata = connector_metadata
+                    .take()
+                    .unwrap_or_else(|| serde_json::json!({}));
+                if let Some(metadata) = metadata.as_object_mut() {
+                    metadata.insert(
+                        "remaining_authorization_amount".to_string(),
+                        remaining_authorization_amount.into(),
+                    );
+                }
+                *connector_metadata = Some(metadata);
+            }
+            response
+        });
+
+        Ok(Self {
+            status,
+            response,
+            ..router_data
+        })
+    }
+}
+
 impl<F, T> TryFrom<types::ResponseRouterData<F, FiservSyncResponse, T, types::PaymentsResponseData>>
     for types::RouterData<F, T, types::PaymentsResponseData>
 {
@@ -391,8 +718,11 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, FiservSyncResponse, T, types::Pa
         };
 
         Ok(Self {
-            status: enums::AttemptStatus::from(
+            // Syncs report the state of the authorization
+            status: get_attempt_status(
                 gateway_resp.gateway_response.transaction_state.clone(),
+                gateway_resp.processor_response_details.as_ref(),
+                enums::AttemptStatus::AuthorizationFailed,
             ),
             response: Ok(types::PaymentsResponseData::TransactionResponse {
                 resource_id: types::ResponseId::ConnectorTransactionId(
@@ -404,7 +734,9 @@ impl<F, T> TryFrom<types::ResponseRouterData<F, FiservSyncResponse, T, types::Pa
                 ),
                 redirection_data: None,
                 mandate_reference: None,
-                connector_metadata: None,
+                connector_metadata: get_processor_response_metadata(
+                    gateway_resp.processor_response_details.as_ref(),
+                ),
                 network_txn_id: None,
                 connector_response_reference_id: Some(
                     gateway_resp
@@ -497,24 +829,44 @@ impl TryFrom<&FiservRouterData<&types::PaymentsCaptureRouterData>> for FiservCap
 #[serde(rename_all = "camelCase")]
 pub struct FiservSyncRequest {
     merchant_details: MerchantDetails,
-    reference_transaction_details: ReferenceTransactionDetails,
+    reference_transaction_details: SyncReferenceTransactionDetails,
+}
+
+/// Transaction inquiry looks up a transaction either by the Fiserv transaction id or by the
+/// merchant transaction id sent while creating it
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReferenceTransactionDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference_transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference_merchant_transaction_id: Option<String>,
 }
 
 impl TryFrom<&types::PaymentsSyncRouterData> for FiservSyncRequest {
     type Error = error_stack::Report<errors::ConnectorError>;
     fn try_from(item: &types::PaymentsSyncRouterData) -> Result<Self, Self::Error> {
         let auth: FiservAuthType = FiservAuthType::try_from(&item.connector_auth_type)?;
+        // Query by the merchant transaction id only when the connector transaction id was lost,
+        // e.g. due to a timeout while authorizing
+        let reference_transaction_details = match item.request.get_connector_transaction_id() {
+            Ok(connector_transaction_id) => SyncReferenceTransactionDetails {
+                reference_transaction_id: Some(connector_transaction_id),
+                reference_merchant_transaction_id: None,
+            },
+            Err(_) => SyncReferenceTransactionDetails {
+                reference_transaction_id: None,
+                reference_merchant_transaction_id: Some(
+                    item.connector_request_reference_id.clone(),
+                ),
+            },
+        };
         Ok(Self {
             merchant_details: MerchantDetails {
                 merchant_id: auth.merchant_account,
                 terminal_id: None,
             },
-            reference_transaction_details: ReferenceTransactionDetails {
-                reference_transaction_id: item
-                    .request
-                    .get_connector_transaction_id()
-                    .change_context(errors::ConnectorError::MissingConnectorTransactionID)?,
-            },
+            reference_transaction_details,
         })
     }
 }
@@ -528,12 +880,14 @@ impl TryFrom<&types::RefundSyncRouterData> for FiservSyncRequest {
                 merchant_id: auth.merchant_account,
                 terminal_id: None,
             },
-            reference_transaction_details: ReferenceTransactionDetails {
-                reference_transaction_id: item
-                    .request
-                    .connector_refund_id
-                    .clone()
-                    .ok_or(errors::ConnectorError::RequestEncodingFailed)?,
+            reference_transaction_details: SyncReferenceTransactionDetails {
+                reference_transaction_id: Some(
+                    item.request
+                        .connector_refund_id
+                        .clone()
+                        .ok_or(errors::ConnectorError::RequestEncodingFailed)?,
+                ),
+                reference_merchant_transaction_id: None,
             },
         })
     }
@@ -639,3 +993,613 @@ impl TryFrom<types::RefundsResponseRouterData<api::RSync, FiservSyncResponse>>
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::marker::PhantomData;
+
+    use hyperswitch_domain_models::router_request_types::SurchargeDetails;
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_router_data<F, Req, Res>(request: Req) -> types::RouterData<F, Req, Res> {
+        types::RouterData {
+            flow: PhantomData,
+            merchant_id: "merchant_123".to_string(),
+            customer_id: None,
+            connector_customer: None,
+            connector: "fiserv".to_string(),
+            payment_id: "pay_123".to_string(),
+            attempt_id: "pay_123_1".to_string(),
+            status: enums::AttemptStatus::default(),
+            payment_method: enums::PaymentMethod::Card,
+            connector_auth_type: types::ConnectorAuthType::SignatureKey {
+                api_key: Secret::new("api_key".to_string()),
+                key1: Secret::new("merchant_account".to_string()),
+                api_secret: Secret::new("fiserv_api_secret_value".to_string()),
+            },
+            description: None,
+            return_url: None,
+            address: types::PaymentAddress::default(),
+            auth_type: enums::AuthenticationType::NoThreeDs,
+            connector_meta_data: Some(Secret::new(json!({ "terminal_id": "terminal_123" }))),
+            connector_wallets_details: None,
+            amount_captured: None,
+            access_token: None,
+            session_token: None,
+            reference_id: None,
+            payment_method_token: None,
+            recurring_mandate_payment_data: None,
+            preprocessing_id: None,
+            payment_method_balance: None,
+            connector_api_version: None,
+            request,
+            response: Err(types::ErrorResponse::default()),
+            connector_request_reference_id: "ref_123".to_string(),
+            #[cfg(feature = "payouts")]
+            payout_method_data: None,
+            #[cfg(feature = "payouts")]
+            quote_id: None,
+            test_mode: None,
+            connector_http_status_code: None,
+            external_latency: None,
+            apple_pay_flow: None,
+            frm_metadata: None,
+            dispute_id: None,
+            refund_id: None,
+            connector_response: None,
+            payment_method_status: None,
+            minor_amount_captured: None,
+        }
+    }
+
+    fn get_fiserv_router_data<T>(router_data: T) -> FiservRouterData<T> {
+        FiservRouterData::try_from((
+            &api::CurrencyUnit::Base,
+            enums::Currency::USD,
+            1000,
+            router_data,
+        ))
+        .unwrap()
+    }
+
+    fn get_authorize_data() -> types::PaymentsAuthorizeData {
+        types::PaymentsAuthorizeData {
+            payment_method_data: domain::PaymentMethodData::Card(domain::Card {
+                card_number: "4111111111111111".parse().unwrap(),
+                card_exp_month: Secret::new("12".to_string()),
+                card_exp_year: Secret::new("2030".to_string()),
+                card_cvc: Secret::new("123".to_string()),
+                ..Default::default()
+            }),
+            amount: 1000,
+            email: None,
+            customer_name: None,
+            currency: enums::Currency::USD,
+            confirm: true,
+            statement_descriptor_suffix: None,
+            statement_descriptor: None,
+            capture_method: Some(enums::CaptureMethod::Manual),
+            router_return_url: None,
+            webhook_url: None,
+            complete_authorize_url: None,
+            setup_future_usage: None,
+            mandate_id: None,
+            off_session: None,
+            customer_acceptance: None,
+            setup_mandate_details: None,
+            browser_info: None,
+            order_details: None,
+            order_category: None,
+            session_token: None,
+            enrolled_for_3ds: false,
+            related_transaction_id: None,
+            payment_experience: None,
+            payment_method_type: None,
+            surcharge_details: None,
+            customer_id: None,
+            request_incremental_authorization: false,
+            metadata: None,
+            authentication_data: None,
+            charges: None,
+            minor_amount: types::MinorUnit::new(1000),
+        }
+    }
+
+    #[test]
+    fn test_auth_type_rejects_blank_or_short_api_secret() {
+        let auth_type = |api_secret: &str| types::ConnectorAuthType::SignatureKey {
+            api_key: Secret::new("api_key".to_string()),
+            key1: Secret::new("merchant_account".to_string()),
+            api_secret: Secret::new(api_secret.to_string()),
+        };
+
+        assert!(FiservAuthType::try_from(&auth_type("fiserv_api_secret_value")).is_ok());
+        assert!(FiservAuthType::try_from(&auth_type("MySecretKey")).is_ok());
+        for api_secret in ["", "                    ", "short"] {
+            assert!(
+                FiservAuthType::try_from(&auth_type(api_secret)).is_err_and(|error| matches!(
+                    error.current_context(),
+                    errors::ConnectorError::InvalidConnectorConfig {
+                        config: "api_secret"
+                    }
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_fiserv_amount_is_in_major_units() {
+        assert_eq!(
+            get_fiserv_amount(12345, enums::Currency::USD).unwrap(),
+            "123.45"
+        );
+        assert_eq!(get_fiserv_amount(5, enums::Currency::USD).unwrap(), "0.05");
+        assert_eq!(get_fiserv_amount(0, enums::Currency::USD).unwrap(), "0.00");
+        assert_eq!(
+            get_fiserv_amount(12345, enums::Currency::JPY).unwrap(),
+            "12345"
+        );
+        assert_eq!(
+            get_fiserv_amount(12345, enums::Currency::KWD).unwrap(),
+            "12.345"
+        );
+        assert!(get_fiserv_amount(-1, enums::Currency::USD).is_err());
+
+        let router_data: types::PaymentsAuthorizeRouterData = get_router_data(get_authorize_data());
+        let fiserv_router_data = FiservRouterData::try_from((
+            &api::CurrencyUnit::Base,
+            enums::Currency::USD,
+            12345,
+            &router_data,
+        ))
+        .unwrap();
+        let request = FiservPaymentsRequest::try_from(&fiserv_router_data).unwrap();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["amount"]["total"],
+            json!(123.45)
+        );
+    }
+
+    #[test]
+    fn test_authorize_request_body() {
+        let router_data: types::PaymentsAuthorizeRouterData = get_router_data(get_authorize_data());
+
+        let request =
+            FiservPaymentsRequest::try_from(&get_fiserv_router_data(&router_data)).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "amount": { "total": 10.0, "currency": "USD" },
+                "source": {
+                    "sourceType": "PaymentCard",
+                    "card": {
+                        "cardData": "4111111111111111",
+                        "expirationMonth": "12",
+                        "expirationYear": "2030",
+                        "securityCode": "123"
+                    }
+                },
+                "transactionDetails": {
+                    "captureFlag": false,
+                    "reversalReasonCode": null,
+                    "merchantTransactionId": "ref_123"
+                },
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": "terminal_123"
+                },
+                "transactionInteraction": {
+                    "origin": "ECOM",
+                    "eciIndicator": "CHANNEL_ENCRYPTED",
+                    "posConditionCode": "CARD_NOT_PRESENT_ECOM"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_authorize_response_keeps_processor_details() {
+        let response: FiservPaymentsResponse = serde_json::from_value(json!({
+            "gatewayResponse": {
+                "gatewayTransactionId": "gtxn_123",
+                "transactionState": "AUTHORIZED",
+                "transactionProcessingDetails": {
+                    "orderId": "order_123",
+                    "transactionId": "txn_123"
+                }
+            },
+            "processorResponseDetails": {
+                "approvalCode": "OK5882",
+                "referenceNumber": "845366457890",
+                "responseCode": "000"
+            }
+        }))
+        .unwrap();
+
+        let router_data = types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_router_data(get_authorize_data()),
+            http_code: 201,
+        })
+        .unwrap();
+
+        let connector_metadata = match router_data.response {
+            Ok(types::PaymentsResponseData::TransactionResponse {
+                connector_metadata, ..
+            }) => connector_metadata,
+            _ => None,
+        };
+        assert_eq!(
+            connector_metadata,
+            Some(json!({
+                "approval_code": "OK5882",
+                "retrieval_reference_number": "845366457890",
+                "processor_response_code": "000"
+            }))
+        );
+    }
+
+    #[test]
+    fn test_authorize_response_maps_decline_codes() {
+        let test_cases = [
+            (
+                "51",
+                enums::AttemptStatus::AuthorizationFailed,
+                Some("insufficient_funds"),
+            ),
+            (
+                "05",
+                enums::AttemptStatus::AuthorizationFailed,
+                Some("do_not_honor"),
+            ),
+            (
+                "54",
+                enums::AttemptStatus::AuthorizationFailed,
+                Some("expired_card"),
+            ),
+            (
+                "N7",
+                enums::AttemptStatus::AuthorizationFailed,
+                Some("incorrect_cvc"),
+            ),
+            (
+                "96",
+                enums::AttemptStatus::Failure,
+                Some("processing_error"),
+            ),
+            ("999", enums::AttemptStatus::Failure, None),
+        ];
+
+        for (response_code, expected_status, expected_decline_reason) in test_cases {
+            let response: FiservPaymentsResponse = serde_json::from_value(json!({
+                "gatewayResponse": {
+                    "transactionState": "DECLINED",
+                    "transactionProcessingDetails": {
+                        "orderId": "order_123",
+                        "transactionId": "txn_123"
+                    }
+                },
+                "processorResponseDetails": {
+                    "responseCode": response_code
+                }
+            }))
+            .unwrap();
+
+            let router_data =
+                types::PaymentsAuthorizeRouterData::try_from(types::ResponseRouterData {
+                    response,
+                    data: get_router_data(get_authorize_data()),
+                    http_code: 201,
+                })
+                .unwrap();
+
+            assert_eq!(router_data.status, expected_status, "{response_code}");
+            let connector_metadata = match router_data.response {
+                Ok(types::PaymentsResponseData::TransactionResponse {
+                    connector_metadata, ..
+                }) => connector_metadata,
+                _ => None,
+            }
+            .unwrap();
+            assert_eq!(
+                connector_metadata["processor_response_code"],
+                json!(response_code)
+            );
+            assert_eq!(
+                connector_metadata.get("decline_reason"),
+                expected_decline_reason.map(|reason| json!(reason)).as_ref(),
+                "{response_code}"
+            );
+        }
+
+        // Issuer declines of a capture fail the capture rather than the authorization
+        for (response_code, expected_status) in [
+            ("51", enums::AttemptStatus::CaptureFailed),
+            ("96", enums::AttemptStatus::Failure),
+        ] {
+            let response: FiservPaymentsResponse = serde_json::from_value(json!({
+                "gatewayResponse": {
+                    "transactionState": "DECLINED",
+                    "transactionProcessingDetails": {
+                        "orderId": "order_123",
+                        "transactionId": "txn_123"
+                    }
+                },
+                "processorResponseDetails": {
+                    "responseCode": response_code
+                }
+            }))
+            .unwrap();
+
+            let router_data =
+                types::PaymentsCaptureRouterData::try_from(types::ResponseRouterData {
+                    response,
+                    data: get_router_data(types::PaymentsCaptureData::default()),
+                    http_code: 201,
+                })
+                .unwrap();
+
+            assert_eq!(router_data.status, expected_status, "{response_code}");
+        }
+    }
+
+    #[test]
+    fn test_surcharged_authorize_request_body() {
+        let mut router_data: types::PaymentsAuthorizeRouterData =
+            get_router_data(get_authorize_data());
+        router_data.request.amount = 1150;
+        router_data.request.minor_amount = types::MinorUnit::new(1150);
+        router_data.request.surcharge_details = Some(SurchargeDetails {
+            original_amount: types::MinorUnit::new(1000),
+            surcharge: common_utils::types::Surcharge::Fixed(types::MinorUnit::new(100)),
+            tax_on_surcharge: None,
+            surcharge_amount: types::MinorUnit::new(100),
+            tax_on_surcharge_amount: types::MinorUnit::new(50),
+            final_amount: types::MinorUnit::new(1150),
+        });
+
+        let fiserv_router_data = FiservRouterData::try_from((
+            &api::CurrencyUnit::Base,
+            enums::Currency::USD,
+            1150,
+            &router_data,
+        ))
+        .unwrap();
+        let request = FiservPaymentsRequest::try_from(&fiserv_router_data).unwrap();
+        let request = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(
+            request.get("amount"),
+            Some(&json!({ "total": 11.5, "currency": "USD" }))
+        );
+        assert_eq!(
+            request.get("amountDetails"),
+            Some(&json!({ "subtotal": 10.0, "tax": 0.5, "surcharge": 1.0 }))
+        );
+
+        router_data.request.amount = 1200;
+        assert!(FiservPaymentsRequest::try_from(&get_fiserv_router_data(&router_data)).is_err());
+    }
+
+    #[test]
+    fn test_capture_request_body() {
+        let router_data: types::PaymentsCaptureRouterData =
+            get_router_data(types::PaymentsCaptureData {
+                amount_to_capture: 1000,
+                currency: enums::Currency::USD,
+                connector_transaction_id: "txn_123".to_string(),
+                payment_amount: 1000,
+                ..Default::default()
+            });
+
+        let request =
+            FiservCaptureRequest::try_from(&get_fiserv_router_data(&router_data)).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "amount": { "total": 10.0, "currency": "USD" },
+                "transactionDetails": {
+                    "captureFlag": true,
+                    "reversalReasonCode": null,
+                    "merchantTransactionId": "ref_123"
+                },
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": "terminal_123"
+                },
+                "referenceTransactionDetails": { "referenceTransactionId": "txn_123" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_void_request_body() {
+        let router_data: types::PaymentsCancelRouterData =
+            get_router_data(types::PaymentsCancelData {
+                connector_transaction_id: "txn_123".to_string(),
+                cancellation_reason: Some("requested_by_customer".to_string()),
+                ..Default::default()
+            });
+
+        let request = FiservCancelRequest::try_from(&router_data).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "transactionDetails": {
+                    "captureFlag": null,
+                    "reversalReasonCode": "requested_by_customer",
+                    "merchantTransactionId": "ref_123"
+                },
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": "terminal_123"
+                },
+                "referenceTransactionDetails": { "referenceTransactionId": "txn_123" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_partial_void_request_body() {
+        let cancel_data = |reversal_amount| types::PaymentsCancelData {
+            amount: Some(1000),
+            reversal_amount,
+            currency: Some(enums::Currency::USD),
+            connector_transaction_id: "txn_123".to_string(),
+            cancellation_reason: Some("requested_by_customer".to_string()),
+            ..Default::default()
+        };
+
+        let request =
+            FiservCancelRequest::try_from(&get_router_data(cancel_data(Some(250)))).unwrap();
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["amount"],
+            json!({ "total": 2.5, "currency": "USD" })
+        );
+
+        // Reversing the whole authorized amount is a full void
+        let request =
+            FiservCancelRequest::try_from(&get_router_data(cancel_data(Some(1000)))).unwrap();
+        assert!(serde_json::to_value(&request)
+            .unwrap()
+            .get("amount")
+            .is_none());
+
+        for reversal_amount in [0, -1, 1001] {
+            assert!(
+                FiservCancelRequest::try_from(&get_router_data(cancel_data(Some(reversal_amount))))
+                    .is_err(),
+                "{reversal_amount}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_partial_void_response_keeps_remaining_authorization() {
+        let response: FiservCancelResponse = serde_json::from_value(json!({
+            "gatewayResponse": {
+                "transactionState": "VOIDED",
+                "transactionProcessingDetails": {
+                    "orderId": "order_123",
+                    "transactionId": "txn_123"
+                }
+            }
+        }))
+        .unwrap();
+        let cancel_data = |reversal_amount| types::PaymentsCancelData {
+            amount: Some(1000),
+            reversal_amount,
+            currency: Some(enums::Currency::USD),
+            connector_transaction_id: "txn_123".to_string(),
+            ..Default::default()
+        };
+
+        let router_data = types::PaymentsCancelRouterData::try_from(types::ResponseRouterData {
+            response: response.clone(),
+            data: get_router_data(cancel_data(Some(250))),
+            http_code: 200,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Authorized);
+        let connector_metadata = match router_data.response {
+            Ok(types::PaymentsResponseData::TransactionResponse {
+                connector_metadata, ..
+            }) => connector_metadata,
+            _ => None,
+        }
+        .unwrap();
+        assert_eq!(connector_metadata["remaining_authorization_amount"], 750);
+
+        let router_data = types::PaymentsCancelRouterData::try_from(types::ResponseRouterData {
+            response,
+            data: get_router_data(cancel_data(None)),
+            http_code: 200,
+        })
+        .unwrap();
+        assert_eq!(router_data.status, enums::AttemptStatus::Voided);
+    }
+
+    #[test]
+    fn test_refund_request_body() {
+        let router_data: types::RefundsRouterData<api::Execute> =
+            get_router_data(types::RefundsData {
+                refund_id: "ref_123".to_string(),
+                connector_transaction_id: "txn_123".to_string(),
+                connector_refund_id: None,
+                currency: enums::Currency::USD,
+                payment_amount: 1000,
+                reason: None,
+                webhook_url: None,
+                refund_amount: 1000,
+                connector_metadata: None,
+                browser_info: None,
+                charges: None,
+                minor_payment_amount: types::MinorUnit::new(1000),
+                minor_refund_amount: types::MinorUnit::new(1000),
+            });
+
+        let request = FiservRefundRequest::try_from(&get_fiserv_router_data(&router_data)).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "amount": { "total": 10.0, "currency": "USD" },
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": "terminal_123"
+                },
+                "referenceTransactionDetails": { "referenceTransactionId": "txn_123" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_psync_request_body() {
+        let router_data: types::PaymentsSyncRouterData = get_router_data(types::PaymentsSyncData {
+            connector_transaction_id: types::ResponseId::ConnectorTransactionId(
+                "txn_123".to_string(),
+            ),
+            ..Default::default()
+        });
+
+        let request = FiservSyncRequest::try_from(&router_data).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": null
+                },
+                "referenceTransactionDetails": { "referenceTransactionId": "txn_123" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_psync_request_body_without_connector_transaction_id() {
+        let router_data: types::PaymentsSyncRouterData = get_router_data(types::PaymentsSyncData {
+            connector_transaction_id: types::ResponseId::NoResponseId,
+            ..Default::default()
+        });
+
+        let request = FiservSyncRequest::try_from(&router_data).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "merchantDetails": {
+                    "merchantId": "merchant_account",
+                    "terminalId": null
+                },
+                "referenceTransactionDetails": { "referenceMerchantTransactionId": "ref_123" }
+            })
+        );
+    }
+}
@@ -21,6 +21,7 @@ use crate::{
     consts,
     core::{
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payment_methods,
         payments::helpers,
         routing::helpers as routing_helpers,
         utils as core_utils,
@@ -1442,6 +1443,51 @@ pub async fn check_merchant_account_kv_status(
     ))
 }
 
+pub async fn purge_merchant_tokens(
+    state: SessionState,
+    merchant_id: String,
+) -> RouterResponse<api_models::admin::MerchantTokensPurgeResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let purged_tokens_count =
+        payment_methods::vault::Vault::purge_merchant_tokens(&state, &key_store).await?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::MerchantTokensPurgeResponse {
+            merchant_id,
+            purged_tokens_count,
+        },
+    ))
+}
+
+pub async fn get_merchant_token_counts(
+    state: SessionState,
+    merchant_id: String,
+) -> RouterResponse<api_models::admin::MerchantTokenCountsResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let token_counts = payment_methods::vault::Vault::token_counts_by_type(&state, &key_store)
+        .await?
+        .into_iter()
+        .map(|(kind, count)| (kind.to_string(), count))
+        .collect();
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::MerchantTokenCountsResponse {
+            merchant_id,
+            token_counts,
+        },
+    ))
+}
+
 pub fn get_frm_config_as_secret(
     frm_configs: Option<Vec<api_models::admin::FrmConfigs>>,
 ) -> Option<Vec<Secret<serde_json::Value>>> {
@@ -245,6 +245,11 @@ pub enum VaultError {
     PaymentMethodNotSupported,
     #[error("The given payout method is currently not supported in vault")]
     PayoutMethodNotSupported,
+    #[error("Currency {currency} is not supported for {payout_method} payouts")]
+    PayoutCurrencyNotSupported {
+        currency: String,
+        payout_method: String,
+    },
     #[error("Missing required field: {field_name}")]
     MissingRequiredField { field_name: &'static str },
     #[error("The card vault returned an unexpected response: {0:?}")]
@@ -257,6 +262,24 @@ pub enum VaultError {
     SavePaymentMethodFailed,
     #[error("Failed to generate fingerprint")]
     GenerateFingerprintFailed,
+    #[error("The vault backend is currently unavailable")]
+    BackendUnavailable,
+    #[error("The vault backend did not respond in time")]
+    BackendTimeout,
+    #[error("Too many vault operations are in flight")]
+    BackendBusy,
+    #[error("The stored values of the token hold different payment methods")]
+    TokenMethodMismatch,
+    #[error("Failed to decrypt vault payload encrypted with key version {key_version:?}")]
+    DecryptionFailed { key_version: Option<u8> },
+    #[error("The stored token is corrupt: {reason}")]
+    CorruptToken { reason: &'static str },
+    #[error("The token was created for a different tenant")]
+    TenantMismatch,
+    #[error("The token was stored by a different service")]
+    ServiceNameMismatch,
+    #[error("Failed to decode the {field} field of the stored token")]
+    FieldDecodeFailed { field: &'static str },
 }
 
 #[derive(Debug, thiserror::Error)]
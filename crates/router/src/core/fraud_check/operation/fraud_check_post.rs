@@ -204,6 +204,7 @@ impl<F: Send + Clone> Domain<F> for FraudCheckPost {
             let cancel_req = api_models::payments::PaymentsCancelRequest {
                 payment_id: frm_data.payment_intent.payment_id.clone(),
                 cancellation_reason: frm_data.fraud_check.frm_error.clone(),
+                amount_to_reverse: None,
                 merchant_connector_details: None,
             };
             let cancel_res = Box::pin(payments::payments_core::<
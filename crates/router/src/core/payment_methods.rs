@@ -28,6 +28,7 @@ pub async fn retrieve_payment_method(
     payment_intent: &PaymentIntent,
     payment_attempt: &PaymentAttempt,
     merchant_key_store: &domain::MerchantKeyStore,
+    billing_address: Option<&api_models::payments::Address>,
 ) -> RouterResult<(Option<payments::PaymentMethodData>, Option<String>)> {
     match pm_data {
         pm_opt @ Some(pm @ api::PaymentMethodData::Card(_)) => {
@@ -38,6 +39,7 @@ pub async fn retrieve_payment_method(
                 enums::PaymentMethod::Card,
                 pm,
                 merchant_key_store,
+                billing_address,
             )
             .await?;
 
@@ -60,6 +62,7 @@ pub async fn retrieve_payment_method(
                 enums::PaymentMethod::BankTransfer,
                 pm,
                 merchant_key_store,
+                billing_address,
             )
             .await?;
 
@@ -73,6 +76,7 @@ pub async fn retrieve_payment_method(
                 enums::PaymentMethod::Wallet,
                 pm,
                 merchant_key_store,
+                billing_address,
             )
             .await?;
 
@@ -86,6 +90,7 @@ pub async fn retrieve_payment_method(
                 enums::PaymentMethod::BankRedirect,
                 pm,
                 merchant_key_store,
+                billing_address,
             )
             .await?;
 
@@ -1954,7 +1954,12 @@ pub async fn list_payment_methods(
         }
 
         // Refreshing our CGraph cache
-        let graph = refresh_pm_filters_cache(&state, &key, builder.build()).await;
+        let graph = request::record_constraint_graph_build(
+            "pm_filters_graph",
+            || builder.build(),
+            |graph| Some(graph),
+        );
+        let graph = refresh_pm_filters_cache(&state, &key, graph).await;
 
         for mca in &filtered_mcas {
             let payment_methods = match &mca.payment_methods_enabled {
@@ -3939,19 +3944,25 @@ impl TempLockerCardSupport {
             .encode_to_string_of_json()
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Wrapped value2 construction failed when saving card to locker")?;
+        let algorithm =
+            vault::get_temp_locker_encryption_algorithm(state, &merchant_key_store.merchant_id)
+                .await?;
 
-        let lookup_key = vault::create_tokenize(
+        let (lookup_key, _) = vault::create_tokenize(
             state,
             value1,
             Some(value2),
             payment_token.to_string(),
             merchant_key_store.key.get_inner(),
+            algorithm,
+            None,
         )
         .await?;
         vault::add_delete_tokenized_data_task(
             &*state.store,
             &lookup_key,
             enums::PaymentMethod::Card,
+            &state.conf.locker.delete_tokenized_data_task_tag,
         )
         .await?;
         metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
@@ -593,6 +593,7 @@ pub fn mk_card_value2(
         external_id,
         customer_id,
         payment_method_id,
+        billing_address: None,
     };
     let value2_req = value2
         .encode_to_string_of_json()
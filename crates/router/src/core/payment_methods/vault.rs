@@ -1,18 +1,27 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
 use common_enums::PaymentMethodType;
 use common_utils::{
-    crypto::{DecodeMessage, EncodeMessage, GcmAes256},
+    consts::ID_LENGTH,
+    crypto::{ChaCha20Poly1305, DecodeMessage, EncodeMessage, GcmAes256, HmacSha256, SignMessage},
     ext_traits::{BytesExt, Encode},
-    generate_id_with_default_len, id_type,
+    generate_id, id_type,
     pii::Email,
 };
 use error_stack::{report, ResultExt};
 use masking::PeekInterface;
+use once_cell::sync::Lazy;
 use router_env::{instrument, tracing};
 use scheduler::{types::process_data, utils as process_tracker_utils};
+use storage_impl::redis::cache::{Cache, CacheKey};
 
 #[cfg(feature = "payouts")]
 use crate::types::api::payouts;
 use crate::{
+    configs::settings::TempLockerEncryptionAlgorithm,
     consts,
     core::errors::{self, CustomResult, RouterResult},
     db, logger, routes,
@@ -24,10 +33,107 @@ use crate::{
     utils::StringExt,
 };
 const VAULT_SERVICE_NAME: &str = "CARD";
+const PAYOUT_TOKEN_ID_LENGTH: usize = 32;
+/// Version of the key temporary locker payloads are encrypted with, stored as the first byte of
+/// the payload
+const TEMP_LOCKER_KEY_VERSION: u8 = 1;
+/// Version reported for temporary locker payloads stored before payloads were versioned
+const LEGACY_TOKEN_VERSION: u8 = 0;
+/// Header bytes identifying the algorithm a temporary locker payload is encrypted with, stored
+/// right after the key version
+const TEMP_LOCKER_GCM_AES_256_HEADER: u8 = 1;
+const TEMP_LOCKER_CHACHA20_POLY1305_HEADER: u8 = 2;
+/// Header byte of temporary locker payloads whose value1 and value2 are encrypted separately, each
+/// with their own algorithm header, stored right after the key version
+const TEMP_LOCKER_SPLIT_VALUES_HEADER: u8 = 3;
+/// Number of keys requested per redis scan call when purging the tokens of a merchant
+const TEMP_LOCKER_PURGE_SCAN_COUNT: u32 = 1000;
+/// Number of payloads fetched and deleted together when purging the tokens of a merchant
+const TEMP_LOCKER_PURGE_BATCH_SIZE: usize = 100;
+/// Minimum time between two token counts of the same merchant, as each count scans the whole
+/// locker
+const TEMP_LOCKER_TOKEN_COUNT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+/// Extends the time to live of the token in `KEYS[1]` to `ARGV[2]` seconds from now, never beyond
+/// the unix timestamp `ARGV[3]`, if the token still holds the payload `ARGV[1]`. The time to live
+/// is never shortened. Returns 1 if the token holds the payload, 0 otherwise.
+const EXTEND_TOKEN_TTL_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+    return 0
+end
+local now = tonumber(redis.call('TIME')[1])
+local expire_at = math.min(now + tonumber(ARGV[2]), tonumber(ARGV[3]))
+local ttl = redis.call('TTL', KEYS[1])
+if ttl >= 0 and now + ttl < expire_at then
+    redis.call('EXPIREAT', KEYS[1], expire_at)
+end
+return 1
+"#;
+
+/// Time of the last token count of each merchant
+static TEMP_LOCKER_TOKEN_COUNTED_AT: Lazy<std::sync::Mutex<HashMap<String, std::time::Instant>>> =
+    Lazy::new(Default::default);
+
+/// Latest token counts of each merchant, summed across merchants into the reported gauges
+static TEMP_LOCKER_TOKEN_COUNTS: Lazy<
+    std::sync::Mutex<HashMap<String, HashMap<VaultPaymentMethodKind, usize>>>,
+> = Lazy::new(Default::default);
+
+/// The kind of data a temporary locker lookup key is generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultKeyKind {
+    PaymentToken,
+    PayoutToken,
+}
+
+impl VaultKeyKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::PaymentToken => "token",
+            Self::PayoutToken => "temporary_token",
+        }
+    }
+
+    /// Payout tokens outlive payment tokens, so they are generated with a longer id
+    fn id_length(self) -> usize {
+        match self {
+            Self::PaymentToken => ID_LENGTH,
+            Self::PayoutToken => PAYOUT_TOKEN_ID_LENGTH,
+        }
+    }
+
+    pub fn generate_lookup_key(self) -> String {
+        generate_id(self.id_length(), self.prefix())
+    }
+
+    /// Derives the lookup key from an idempotency key, so that retries of the same logical request
+    /// by the merchant map to the same token. The hash is keyed with the key of the merchant, so
+    /// lookup keys cannot be derived from the idempotency key alone.
+    fn derive_lookup_key(
+        self,
+        hash_key: &[u8],
+        merchant_id: &str,
+        idempotency_key: &str,
+    ) -> Option<String> {
+        generate_dedup_hash(hash_key, &format!("{merchant_id}:{idempotency_key}"))
+            .map(|hash| format!("{}_{hash}", self.prefix()))
+    }
+}
 
 pub struct SupplementaryVaultData {
     pub customer_id: Option<id_type::CustomerId>,
     pub payment_method_id: Option<String>,
+    pub billing_address: Option<api_models::payments::Address>,
+    /// Key version of the payload the token was read from, `LEGACY_TOKEN_VERSION` for payloads
+    /// stored before they were versioned
+    pub token_version: u8,
+}
+
+impl SupplementaryVaultData {
+    /// Whether the token was stored in an older format, and should be re-vaulted into the current
+    /// one
+    pub fn is_token_outdated(&self) -> bool {
+        self.token_version < TEMP_LOCKER_KEY_VERSION
+    }
 }
 
 pub trait Vaultable: Sized {
@@ -45,6 +151,86 @@ pub trait Vaultable: Sized {
         value1: String,
         value2: String,
     ) -> CustomResult<(Self, SupplementaryVaultData), errors::VaultError>;
+    /// Same as `from_values`, but never carries the customer id through, for flows which must not
+    /// associate the method with a customer.
+    fn from_values_anonymized(
+        value1: String,
+        value2: String,
+    ) -> CustomResult<(Self, SupplementaryVaultData), errors::VaultError> {
+        Self::from_values(value1, value2).map(|(method, supp_data)| {
+            (
+                method,
+                SupplementaryVaultData {
+                    customer_id: None,
+                    ..supp_data
+                },
+            )
+        })
+    }
+    /// Stable hash of the identifying details of the method, keyed by a merchant scoped secret.
+    /// `None` when the method cannot be deduplicated.
+    fn dedup_hash(&self, _hash_key: &[u8]) -> Option<String> {
+        None
+    }
+}
+
+/// Implementers relying on the default `get_value2` silently drop the customer id, so warn when a
+/// customer id is provided but value2 is blank
+fn warn_if_customer_id_not_stored<V: Vaultable>(
+    value2: &str,
+    customer_id: Option<&id_type::CustomerId>,
+) {
+    if customer_id.is_some() && value2.trim().is_empty() {
+        logger::warn!(
+            "Customer id was provided but is not stored in value2 for {}",
+            std::any::type_name::<V>()
+        );
+    }
+}
+
+/// Checks that the payment method read from a token is of the expected payment method, if any
+fn verify_token_payment_method(
+    payment_method: &api::PaymentMethodData,
+    expected_payment_method: Option<enums::PaymentMethod>,
+) -> CustomResult<(), errors::VaultError> {
+    match expected_payment_method {
+        Some(expected) if payment_method.get_payment_method() != Some(expected) => {
+            Err(report!(errors::VaultError::TokenMethodMismatch)).attach_printable(format!(
+                "Expected a {expected} payment method but the token holds a {:?} payment method",
+                payment_method.get_payment_method()
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn generate_dedup_hash(hash_key: &[u8], hash_string: &str) -> Option<String> {
+    HmacSha256::sign_message(&HmacSha256, hash_key, hash_string.as_bytes())
+        .map(hex::encode)
+        .map_err(|error| logger::error!(?error, "Failed to generate dedup hash"))
+        .ok()
+}
+
+fn get_card_value2(
+    card: &api::Card,
+    customer_id: Option<id_type::CustomerId>,
+    store_cvc: bool,
+    billing_address: Option<&api_models::payments::Address>,
+    payment_method_id: Option<String>,
+) -> CustomResult<String, errors::VaultError> {
+    let value2 = api::TokenizedCardValue2 {
+        card_security_code: store_cvc.then(|| card.card_cvc.peek().clone()),
+        card_fingerprint: None,
+        external_id: None,
+        customer_id,
+        payment_method_id,
+        billing_address: billing_address.cloned(),
+    };
+
+    value2
+        .encode_to_string_of_json()
+        .change_context(errors::VaultError::RequestEncodingFailed)
+        .attach_printable("Failed to encode card value2")
 }
 
 impl Vaultable for api::Card {
@@ -75,18 +261,7 @@ impl Vaultable for api::Card {
         &self,
         customer_id: Option<id_type::CustomerId>,
     ) -> CustomResult<String, errors::VaultError> {
-        let value2 = api::TokenizedCardValue2 {
-            card_security_code: Some(self.card_cvc.peek().clone()),
-            card_fingerprint: None,
-            external_id: None,
-            customer_id,
-            payment_method_id: None,
-        };
-
-        value2
-            .encode_to_string_of_json()
-            .change_context(errors::VaultError::RequestEncodingFailed)
-            .attach_printable("Failed to encode card value2")
+        get_card_value2(self, customer_id, true, None, None)
     }
 
     fn from_values(
@@ -103,14 +278,24 @@ impl Vaultable for api::Card {
             .change_context(errors::VaultError::ResponseDeserializationFailed)
             .attach_printable("Could not deserialize into card value2")?;
 
+        let card_cvc = value2.card_security_code.unwrap_or_default();
+        if !card_cvc.chars().all(|c| c.is_ascii_digit()) {
+            return Err(report!(errors::VaultError::FieldDecodeFailed {
+                field: "card_security_code",
+            }))
+            .attach_printable("Invalid card security code format from the mock locker");
+        }
+
         let card = Self {
             card_number: cards::CardNumber::try_from(value1.card_number)
-                .change_context(errors::VaultError::ResponseDeserializationFailed)
+                .change_context(errors::VaultError::FieldDecodeFailed {
+                    field: "card_number",
+                })
                 .attach_printable("Invalid card number format from the mock locker")?,
             card_exp_month: value1.exp_month.into(),
             card_exp_year: value1.exp_year.into(),
             card_holder_name: value1.name_on_card.map(masking::Secret::new),
-            card_cvc: value2.card_security_code.unwrap_or_default().into(),
+            card_cvc: card_cvc.into(),
             card_issuer: None,
             card_network: None,
             bank_code: None,
@@ -122,10 +307,17 @@ impl Vaultable for api::Card {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: value2.payment_method_id,
+            billing_address: value2.billing_address,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((card, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        let hash_string = format!("{}-{}", self.card_number.peek(), enums::PaymentMethod::Card);
+        generate_dedup_hash(hash_key, &hash_string)
+    }
 }
 
 impl Vaultable for api_models::payments::BankTransferData {
@@ -174,6 +366,8 @@ impl Vaultable for api_models::payments::BankTransferData {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: None,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((bank_transfer_data, supp_data))
@@ -226,10 +420,22 @@ impl Vaultable for api::WalletData {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: None,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((wallet, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        match self {
+            Self::PaypalRedirect(paypal) => paypal.email.as_ref().and_then(|email| {
+                let hash_string = format!("{}-{}", email.peek(), PaymentMethodType::Paypal);
+                generate_dedup_hash(hash_key, &hash_string)
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl Vaultable for api_models::payments::BankRedirectData {
@@ -278,10 +484,25 @@ impl Vaultable for api_models::payments::BankRedirectData {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: None,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((bank_transfer_data, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        match self {
+            Self::Giropay {
+                bank_account_iban: Some(iban),
+                ..
+            } => {
+                let hash_string = format!("{}-{}", iban.peek(), PaymentMethodType::Giropay);
+                generate_dedup_hash(hash_key, &hash_string)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -291,6 +512,62 @@ pub enum VaultPaymentMethod {
     Wallet(String),
     BankTransfer(String),
     BankRedirect(String),
+    /// Payment method data of a type registered through `register_custom_vaultable`, stored as
+    /// the type tag of the registered type and its value
+    Custom(String, String),
+}
+
+/// The type tag of a `VaultPaymentMethod`, without its value
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, strum::Display, strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VaultPaymentMethodKind {
+    Card,
+    Wallet,
+    BankTransfer,
+    BankRedirect,
+    Custom,
+}
+
+/// Reads only the type tag of a serialized `VaultPaymentMethod`, leaving its value undecoded
+#[derive(Debug, serde::Deserialize)]
+struct VaultPaymentMethodTag {
+    #[serde(rename = "type")]
+    kind: VaultPaymentMethodKind,
+}
+
+impl VaultPaymentMethodKind {
+    /// Returns the kind of payment method stored in the value1 of a token, or `None` for values
+    /// that are not payment methods, such as payout tokens
+    fn from_value1(value1: &str) -> Option<Self> {
+        serde_json::from_str::<VaultPaymentMethodTag>(value1)
+            .ok()
+            .map(|tag| tag.kind)
+    }
+}
+
+impl VaultPaymentMethod {
+    /// Whether payment method data of the given payment method can be stored in the temporary
+    /// locker. Keep in sync with the variants of `VaultPaymentMethod`.
+    pub fn is_supported(payment_method: enums::PaymentMethod) -> bool {
+        match payment_method {
+            enums::PaymentMethod::Card
+            | enums::PaymentMethod::Wallet
+            | enums::PaymentMethod::BankTransfer
+            | enums::PaymentMethod::BankRedirect => true,
+            enums::PaymentMethod::CardRedirect
+            | enums::PaymentMethod::PayLater
+            | enums::PaymentMethod::Crypto
+            | enums::PaymentMethod::BankDebit
+            | enums::PaymentMethod::Reward
+            | enums::PaymentMethod::RealTimePayment
+            | enums::PaymentMethod::Upi
+            | enums::PaymentMethod::Voucher
+            | enums::PaymentMethod::GiftCard => false,
+        }
+    }
 }
 
 impl Vaultable for api::PaymentMethodData {
@@ -344,6 +621,15 @@ impl Vaultable for api::PaymentMethodData {
         value1: String,
         value2: String,
     ) -> CustomResult<(Self, SupplementaryVaultData), errors::VaultError> {
+        // Truncated or old tokens can hold an empty value2, which would otherwise surface as a
+        // deserialization failure
+        if value2.trim().is_empty() {
+            return Err(report!(errors::VaultError::CorruptToken {
+                reason: "value2 is empty"
+            }))
+            .attach_printable("Payment method value2 is empty");
+        }
+
         let value1: VaultPaymentMethod = value1
             .parse_struct("PaymentMethodValue1")
             .change_context(errors::VaultError::ResponseDeserializationFailed)
@@ -379,11 +665,139 @@ impl Vaultable for api::PaymentMethodData {
                     api_models::payments::BankRedirectData::from_values(mvalue1, mvalue2)?;
                 Ok((Self::BankRedirect(bank_redirect), supp_data))
             }
+            (VaultPaymentMethod::Custom(..), VaultPaymentMethod::Custom(..)) => {
+                Err(errors::VaultError::PaymentMethodNotSupported).attach_printable(
+                    "Custom payment method data must be decoded with decode_custom_vault_values",
+                )
+            }
 
             _ => Err(errors::VaultError::PaymentMethodNotSupported)
                 .attach_printable("Payment method not supported"),
         }
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        match self {
+            Self::Card(card) => card.dedup_hash(hash_key),
+            Self::Wallet(wallet) => wallet.dedup_hash(hash_key),
+            Self::BankRedirect(bank_redirect) => bank_redirect.dedup_hash(hash_key),
+            // None of the bank transfer variants carry an account number or IBAN on the payment
+            // side, so they fall back to the default of not being deduplicated
+            Self::BankTransfer(bank_transfer) => bank_transfer.dedup_hash(hash_key),
+            _ => None,
+        }
+    }
+}
+
+/// Payment method data of a type defined outside this crate that can be stored in the temporary
+/// locker, identified by a type tag unique across the registered types
+pub trait CustomVaultable: Vaultable + Send + 'static {
+    const TYPE_TAG: &'static str;
+}
+
+type CustomVaultableDecoder = fn(
+    String,
+    String,
+) -> CustomResult<
+    (Box<dyn std::any::Any + Send>, SupplementaryVaultData),
+    errors::VaultError,
+>;
+
+/// Decoders of the registered custom vaultable types, keyed by type tag
+static CUSTOM_VAULTABLE_REGISTRY: Lazy<RwLock<HashMap<&'static str, CustomVaultableDecoder>>> =
+    Lazy::new(Default::default);
+
+fn decode_custom_vaultable<T: CustomVaultable>(
+    value1: String,
+    value2: String,
+) -> CustomResult<(Box<dyn std::any::Any + Send>, SupplementaryVaultData), errors::VaultError> {
+    let (data, supp_data) = T::from_values(value1, value2)?;
+    Ok((Box::new(data), supp_data))
+}
+
+/// Registers a custom vaultable type, so that values stored under its type tag can be decoded.
+/// Registering a type again replaces the previously registered type with the same tag.
+pub fn register_custom_vaultable<T: CustomVaultable>() {
+    CUSTOM_VAULTABLE_REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(T::TYPE_TAG, decode_custom_vaultable::<T>);
+}
+
+fn get_custom_vaultable_decoder(
+    type_tag: &str,
+) -> CustomResult<CustomVaultableDecoder, errors::VaultError> {
+    CUSTOM_VAULTABLE_REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(type_tag)
+        .copied()
+        .ok_or(errors::VaultError::PaymentMethodNotSupported)
+        .attach_printable_lazy(|| format!("No custom vaultable registered for type {type_tag}"))
+}
+
+/// Encodes custom payment method data into the values stored in the temporary locker
+pub fn get_custom_vault_values<T: CustomVaultable>(
+    data: &T,
+    customer_id: Option<id_type::CustomerId>,
+) -> CustomResult<(String, String), errors::VaultError> {
+    get_custom_vaultable_decoder(T::TYPE_TAG)?;
+
+    let value1 = VaultPaymentMethod::Custom(
+        T::TYPE_TAG.to_string(),
+        data.get_value1(customer_id.clone())?,
+    )
+    .encode_to_string_of_json()
+    .change_context(errors::VaultError::RequestEncodingFailed)
+    .attach_printable("Failed to encode custom payment method value1")?;
+    let value2 = VaultPaymentMethod::Custom(T::TYPE_TAG.to_string(), data.get_value2(customer_id)?)
+        .encode_to_string_of_json()
+        .change_context(errors::VaultError::RequestEncodingFailed)
+        .attach_printable("Failed to encode custom payment method value2")?;
+
+    Ok((value1, value2))
+}
+
+/// Decodes values stored through `get_custom_vault_values` with the decoder registered for their
+/// type tag, returning the type tag along with the decoded data
+pub fn decode_custom_vault_values(
+    value1: String,
+    value2: String,
+) -> CustomResult<
+    (
+        String,
+        Box<dyn std::any::Any + Send>,
+        SupplementaryVaultData,
+    ),
+    errors::VaultError,
+> {
+    let value1: VaultPaymentMethod = value1
+        .parse_struct("PaymentMethodValue1")
+        .change_context(errors::VaultError::ResponseDeserializationFailed)
+        .attach_printable("Could not deserialize into payment method value 1")?;
+
+    let value2: VaultPaymentMethod = value2
+        .parse_struct("PaymentMethodValue2")
+        .change_context(errors::VaultError::ResponseDeserializationFailed)
+        .attach_printable("Could not deserialize into payment method value 2")?;
+
+    match (value1, value2) {
+        (
+            VaultPaymentMethod::Custom(type_tag, mvalue1),
+            VaultPaymentMethod::Custom(value2_type_tag, mvalue2),
+        ) => {
+            if type_tag != value2_type_tag {
+                return Err(errors::VaultError::TokenMethodMismatch).attach_printable(format!(
+                    "Custom payment method values of types {type_tag} and {value2_type_tag}"
+                ));
+            }
+            let decode = get_custom_vaultable_decoder(&type_tag)?;
+            let (data, supp_data) = decode(mvalue1, mvalue2)?;
+            Ok((type_tag, data, supp_data))
+        }
+        _ => Err(errors::VaultError::PaymentMethodNotSupported)
+            .attach_printable("Stored values are not custom payment method data"),
+    }
 }
 
 #[cfg(feature = "payouts")]
@@ -418,6 +832,7 @@ impl Vaultable for api::CardPayout {
             external_id: None,
             customer_id,
             payment_method_id: None,
+            billing_address: None,
         };
 
         value2
@@ -453,10 +868,17 @@ impl Vaultable for api::CardPayout {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: value2.payment_method_id,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((card, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        let hash_string = format!("{}-{}", self.card_number.peek(), enums::PaymentMethod::Card);
+        generate_dedup_hash(hash_key, &hash_string)
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -539,10 +961,185 @@ impl Vaultable for api::WalletPayout {
         let supp_data = SupplementaryVaultData {
             customer_id: value2.customer_id,
             payment_method_id: None,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((wallet, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        let (identifier, wallet_type) = match self {
+            Self::Paypal(paypal) => (
+                paypal
+                    .email
+                    .as_ref()
+                    .map(|email| email.peek().clone())
+                    .or_else(|| paypal.paypal_id.as_ref().map(|id| id.peek().clone())),
+                PaymentMethodType::Paypal,
+            ),
+            Self::Venmo(venmo) => (
+                venmo
+                    .telephone_number
+                    .as_ref()
+                    .map(|number| number.peek().clone()),
+                PaymentMethodType::Venmo,
+            ),
+        };
+
+        identifier.and_then(|identifier| {
+            generate_dedup_hash(hash_key, &format!("{}-{}", identifier, wallet_type))
+        })
+    }
+}
+
+/// Ensures the payout currency can be paid out to the given wallet type.
+#[cfg(feature = "payouts")]
+pub fn validate_wallet_payout_currency(
+    wallet: &api::WalletPayout,
+    currency: enums::Currency,
+) -> CustomResult<(), errors::VaultError> {
+    // An empty list means the wallet has no known currency restriction
+    let (wallet_type, supported_currencies): (PaymentMethodType, &[enums::Currency]) = match wallet
+    {
+        api::WalletPayout::Paypal(_) => (PaymentMethodType::Paypal, &[]),
+        api::WalletPayout::Venmo(_) => (PaymentMethodType::Venmo, &[enums::Currency::USD]),
+    };
+
+    if supported_currencies.is_empty() || supported_currencies.contains(&currency) {
+        Ok(())
+    } else {
+        Err(report!(errors::VaultError::PayoutCurrencyNotSupported {
+            currency: currency.to_string(),
+            payout_method: wallet_type.to_string(),
+        }))
+    }
+}
+
+/// Ensures the wallet type is one the merchant accepts for payouts.
+#[cfg(feature = "payouts")]
+pub fn validate_wallet_payout_type(
+    wallet: &api::WalletPayout,
+    allowed_wallet_types: &[PaymentMethodType],
+) -> CustomResult<(), errors::VaultError> {
+    let wallet_type = match wallet {
+        api::WalletPayout::Paypal(_) => PaymentMethodType::Paypal,
+        api::WalletPayout::Venmo(_) => PaymentMethodType::Venmo,
+    };
+
+    if allowed_wallet_types.contains(&wallet_type) {
+        Ok(())
+    } else {
+        Err(report!(errors::VaultError::PayoutMethodNotSupported))
+            .attach_printable(format!("Payout wallet type {wallet_type} is not allowed"))
+    }
+}
+
+/// Fetches the payout wallet types the merchant accepts, `None` if the merchant has not
+/// restricted them.
+#[cfg(feature = "payouts")]
+async fn get_allowed_payout_wallet_types(
+    state: &routes::SessionState,
+    merchant_id: &str,
+) -> RouterResult<Option<Vec<PaymentMethodType>>> {
+    let key = format!("{merchant_id}_allowed_payout_wallet_types");
+    match state.store.find_config_by_key(&key).await {
+        Ok(config) => config
+            .config
+            .parse_struct("AllowedPayoutWalletTypes")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Allowed payout wallet types config has invalid structure")
+            .map(Some),
+        Err(err) if err.current_context().is_db_not_found() => Ok(None),
+        Err(err) => Err(err)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch allowed payout wallet types config"),
+    }
+}
+
+/// Value2 of a payment method, leaving out the card security code when `store_cvc` is false and
+/// storing the billing address and the id of the saved payment method alongside cards when
+/// provided
+fn get_payment_method_value2(
+    payment_method: &api::PaymentMethodData,
+    customer_id: Option<id_type::CustomerId>,
+    store_cvc: bool,
+    billing_address: Option<&api_models::payments::Address>,
+    payment_method_id: Option<String>,
+) -> CustomResult<String, errors::VaultError> {
+    match payment_method {
+        api::PaymentMethodData::Card(card)
+            if !store_cvc || billing_address.is_some() || payment_method_id.is_some() =>
+        {
+            VaultPaymentMethod::Card(get_card_value2(
+                card,
+                customer_id,
+                store_cvc,
+                billing_address,
+                payment_method_id,
+            )?)
+            .encode_to_string_of_json()
+            .change_context(errors::VaultError::RequestEncodingFailed)
+            .attach_printable("Failed to encode payment method value2")
+        }
+        _ => payment_method.get_value2(customer_id),
+    }
+}
+
+/// Builds the value2 of a payment method replacing the one stored against a token, keeping the
+/// supplementary data of the stored token
+fn get_replaced_payment_method_value2(
+    payment_method: &api::PaymentMethodData,
+    supp_data: SupplementaryVaultData,
+    store_cvc: bool,
+) -> CustomResult<String, errors::VaultError> {
+    get_payment_method_value2(
+        payment_method,
+        supp_data.customer_id,
+        store_cvc,
+        supp_data.billing_address.as_ref(),
+        supp_data.payment_method_id,
+    )
+}
+
+/// Merchants relying on network tokens can opt out of storing the card security code in the
+/// temporary locker, defaults to storing it
+async fn should_store_cvc(state: &routes::SessionState, merchant_id: &str) -> RouterResult<bool> {
+    let key = format!("{merchant_id}_store_cvc_in_temp_locker");
+    match state.store.find_config_by_key(&key).await {
+        Ok(config) => config
+            .config
+            .parse_struct("StoreCvcInTempLocker")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Store cvc config has invalid structure"),
+        Err(err) if err.current_context().is_db_not_found() => Ok(true),
+        Err(err) => Err(err)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch store cvc config"),
+    }
+}
+
+/// Returns the algorithm the temporary locker payloads of the merchant are encrypted with, which
+/// can be overridden per merchant through the `{merchant_id}_temp_locker_encryption_algorithm`
+/// config, e.g. for merchants served from CPUs without AES instructions
+pub async fn get_temp_locker_encryption_algorithm(
+    state: &routes::SessionState,
+    merchant_id: &str,
+) -> RouterResult<TempLockerEncryptionAlgorithm> {
+    let key = format!("{merchant_id}_temp_locker_encryption_algorithm");
+    match state.store.find_config_by_key(&key).await {
+        Ok(config) => config
+            .config
+            .parse_struct("TempLockerEncryptionAlgorithm")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Temp locker encryption algorithm config has invalid structure"),
+        Err(err) if err.current_context().is_db_not_found() => {
+            Ok(state.conf.locker.temp_locker_encryption_algorithm)
+        }
+        Err(err) => Err(err)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch temp locker encryption algorithm config"),
+    }
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -563,6 +1160,28 @@ pub struct TokenizedBankInsensitiveValues {
     pub bank_country_code: Option<api::enums::CountryAlpha2>,
     pub bank_city: Option<String>,
     pub bank_branch: Option<String>,
+    /// Micro-deposit verification status of ACH and SEPA accounts, only set by the verification
+    /// flow through `Vault::set_payout_bank_verification_status`. Absent until then, which is
+    /// treated as unverified.
+    #[cfg(feature = "payouts")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_status: Option<BankVerificationStatus>,
+}
+
+/// Status of the micro-deposit verification of a vaulted payout bank account
+#[cfg(feature = "payouts")]
+#[derive(Default, Eq, PartialEq, Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BankVerificationStatus {
+    /// The bank account has not been verified
+    #[default]
+    Unverified,
+    /// Micro-deposits were sent to the bank account and are yet to be confirmed
+    Pending,
+    /// The bank account was verified through micro-deposits
+    Verified,
+    /// The micro-deposits sent to the bank account could not be confirmed
+    Failed,
 }
 
 #[cfg(feature = "payouts")]
@@ -627,6 +1246,7 @@ impl Vaultable for api::BankPayout {
                 bank_country_code: b.bank_country_code.to_owned(),
                 bank_city: b.bank_city.to_owned(),
                 bank_branch: None,
+                verification_status: None,
             },
             Self::Bacs(b) => TokenizedBankInsensitiveValues {
                 customer_id,
@@ -634,6 +1254,7 @@ impl Vaultable for api::BankPayout {
                 bank_country_code: b.bank_country_code.to_owned(),
                 bank_city: b.bank_city.to_owned(),
                 bank_branch: None,
+                verification_status: None,
             },
             Self::Sepa(bank_details) => TokenizedBankInsensitiveValues {
                 customer_id,
@@ -641,6 +1262,7 @@ impl Vaultable for api::BankPayout {
                 bank_country_code: bank_details.bank_country_code.to_owned(),
                 bank_city: bank_details.bank_city.to_owned(),
                 bank_branch: None,
+                verification_status: None,
             },
             Self::Pix(bank_details) => TokenizedBankInsensitiveValues {
                 customer_id,
@@ -648,6 +1270,7 @@ impl Vaultable for api::BankPayout {
                 bank_country_code: None,
                 bank_city: None,
                 bank_branch: bank_details.bank_branch.to_owned(),
+                verification_status: None,
             },
         };
 
@@ -725,10 +1348,38 @@ impl Vaultable for api::BankPayout {
         let supp_data = SupplementaryVaultData {
             customer_id: bank_insensitive_data.customer_id,
             payment_method_id: None,
+            billing_address: None,
+            token_version: LEGACY_TOKEN_VERSION,
         };
 
         Ok((bank, supp_data))
     }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        let hash_string = match self {
+            Self::Ach(b) => format!(
+                "{}-{}-{}",
+                b.bank_account_number.peek(),
+                b.bank_routing_number.peek(),
+                PaymentMethodType::Ach
+            ),
+            Self::Bacs(b) => format!(
+                "{}-{}-{}",
+                b.bank_account_number.peek(),
+                b.bank_sort_code.peek(),
+                PaymentMethodType::Bacs
+            ),
+            Self::Sepa(b) => format!("{}-{}", b.iban.peek(), PaymentMethodType::Sepa),
+            Self::Pix(b) => format!(
+                "{}-{}-{}",
+                b.bank_account_number.peek(),
+                b.pix_key.peek(),
+                PaymentMethodType::Pix
+            ),
+        };
+
+        generate_dedup_hash(hash_key, &hash_string)
+    }
 }
 
 #[cfg(feature = "payouts")]
@@ -740,6 +1391,63 @@ pub enum VaultPayoutMethod {
     Wallet(String),
 }
 
+#[cfg(feature = "payouts")]
+impl VaultPayoutMethod {
+    fn method_name(&self) -> &'static str {
+        match self {
+            Self::Card(_) => "card",
+            Self::Bank(_) => "bank",
+            Self::Wallet(_) => "wallet",
+        }
+    }
+
+    /// Narrows the payout types enabled for a merchant down to the ones whose payout method data
+    /// can be stored in the temporary locker, keeping the order they were enabled in
+    pub fn get_supported_payout_types(
+        enabled_payout_types: impl IntoIterator<Item = common_enums::PayoutType>,
+    ) -> Vec<common_enums::PayoutType> {
+        enabled_payout_types
+            .into_iter()
+            .filter(|payout_type| {
+                VAULT_PAYOUT_METHOD_TYPES.contains(&payout_type.to_string().as_str())
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "payouts")]
+const VAULT_PAYOUT_METHOD_TYPES: [&str; 3] = ["card", "bank", "wallet"];
+
+#[cfg(feature = "payouts")]
+#[derive(Debug, serde::Deserialize)]
+struct VaultPayoutMethodType {
+    #[serde(rename = "type")]
+    method_type: String,
+}
+
+/// Parses a stored payout method value, telling apart a payout method this build does not know
+/// about from a value which could not be parsed at all
+#[cfg(feature = "payouts")]
+fn parse_vault_payout_method(
+    value: String,
+    type_name: &'static str,
+) -> CustomResult<VaultPayoutMethod, errors::VaultError> {
+    let parsed_value: Result<VaultPayoutMethod, _> = value.parse_struct(type_name);
+    parsed_value.or_else(|error| {
+        let method_type: Result<VaultPayoutMethodType, _> =
+            value.parse_struct("VaultPayoutMethodType");
+        match method_type {
+            Ok(VaultPayoutMethodType { method_type })
+                if !VAULT_PAYOUT_METHOD_TYPES.contains(&method_type.as_str()) =>
+            {
+                Err(errors::VaultError::PayoutMethodNotSupported)
+                    .attach_printable(format!("Payout method {method_type} is not supported"))
+            }
+            _ => Err(error).change_context(errors::VaultError::ResponseDeserializationFailed),
+        }
+    })
+}
+
 #[cfg(feature = "payouts")]
 impl Vaultable for api::PayoutMethodData {
     fn get_value1(
@@ -778,14 +1486,10 @@ impl Vaultable for api::PayoutMethodData {
         value1: String,
         value2: String,
     ) -> CustomResult<(Self, SupplementaryVaultData), errors::VaultError> {
-        let value1: VaultPayoutMethod = value1
-            .parse_struct("VaultMethodValue1")
-            .change_context(errors::VaultError::ResponseDeserializationFailed)
+        let value1 = parse_vault_payout_method(value1, "VaultMethodValue1")
             .attach_printable("Could not deserialize into vault method value 1")?;
 
-        let value2: VaultPayoutMethod = value2
-            .parse_struct("VaultMethodValue2")
-            .change_context(errors::VaultError::ResponseDeserializationFailed)
+        let value2 = parse_vault_payout_method(value2, "VaultMethodValue2")
             .attach_printable("Could not deserialize into vault method value 2")?;
 
         match (value1, value2) {
@@ -801,86 +1505,403 @@ impl Vaultable for api::PayoutMethodData {
                 let (wallet, supp_data) = api::WalletPayout::from_values(mvalue1, mvalue2)?;
                 Ok((Self::Wallet(wallet), supp_data))
             }
-            _ => Err(errors::VaultError::PayoutMethodNotSupported)
-                .attach_printable("Payout method not supported"),
+            (value1, value2) => {
+                Err(errors::VaultError::TokenMethodMismatch).attach_printable(format!(
+                    "Value1 holds a {} payout method but value2 holds a {} payout method",
+                    value1.method_name(),
+                    value2.method_name()
+                ))
+            }
+        }
+    }
+
+    fn dedup_hash(&self, hash_key: &[u8]) -> Option<String> {
+        match self {
+            Self::Card(card) => card.dedup_hash(hash_key),
+            Self::Bank(bank) => bank.dedup_hash(hash_key),
+            Self::Wallet(wallet) => wallet.dedup_hash(hash_key),
         }
     }
 }
 
+#[cfg(feature = "payouts")]
+fn parse_bank_insensitive_values(
+    value2: String,
+) -> CustomResult<TokenizedBankInsensitiveValues, errors::VaultError> {
+    match parse_vault_payout_method(value2, "VaultMethodValue2")? {
+        VaultPayoutMethod::Bank(bank_insensitive_data) => bank_insensitive_data
+            .parse_struct("TokenizedBankValue2")
+            .change_context(errors::VaultError::ResponseDeserializationFailed)
+            .attach_printable("Could not deserialize into bank data bank_insensitive_data"),
+        value2 => Err(errors::VaultError::TokenMethodMismatch).attach_printable(format!(
+            "Value2 holds a {} payout method instead of a bank",
+            value2.method_name()
+        )),
+    }
+}
+
+/// Reads the verification status out of the value2 of a payout bank account token
+#[cfg(feature = "payouts")]
+fn get_bank_verification_status(
+    value2: String,
+) -> CustomResult<BankVerificationStatus, errors::VaultError> {
+    Ok(parse_bank_insensitive_values(value2)?
+        .verification_status
+        .unwrap_or_default())
+}
+
+/// Replaces the verification status in the value2 of a payout bank account token
+#[cfg(feature = "payouts")]
+fn set_bank_verification_status(
+    value2: String,
+    verification_status: BankVerificationStatus,
+) -> CustomResult<String, errors::VaultError> {
+    let bank_insensitive_data = TokenizedBankInsensitiveValues {
+        verification_status: Some(verification_status),
+        ..parse_bank_insensitive_values(value2)?
+    }
+    .encode_to_string_of_json()
+    .change_context(errors::VaultError::RequestEncodingFailed)
+    .attach_printable("Failed to encode bank data bank_insensitive_data")?;
+
+    VaultPayoutMethod::Bank(bank_insensitive_data)
+        .encode_to_string_of_json()
+        .change_context(errors::VaultError::RequestEncodingFailed)
+        .attach_printable("Failed to encode payout method value2")
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MockTokenizeDBValue {
     pub value1: String,
     pub value2: String,
 }
 
+/// Details of a vaulted card which are safe to display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardDisplay {
+    pub last4: String,
+    pub exp_month: masking::Secret<String>,
+    pub exp_year: masking::Secret<String>,
+    pub network: Option<enums::CardNetwork>,
+}
+
+/// Extracts the display safe details and the card isin out of a card value1, without
+/// reconstructing the card
+fn get_card_display_from_value1(
+    value1: String,
+) -> CustomResult<(CardDisplay, String), errors::VaultError> {
+    let value1: VaultPaymentMethod = value1
+        .parse_struct("PaymentMethodValue1")
+        .change_context(errors::VaultError::ResponseDeserializationFailed)
+        .attach_printable("Could not deserialize into payment method value 1")?;
+    let VaultPaymentMethod::Card(card_value1) = value1 else {
+        return Err(report!(errors::VaultError::PaymentMethodNotSupported))
+            .attach_printable("Token does not hold a card");
+    };
+    let card_value1: api::TokenizedCardValue1 = card_value1
+        .parse_struct("TokenizedCardValue1")
+        .change_context(errors::VaultError::ResponseDeserializationFailed)
+        .attach_printable("Could not deserialize into card value1")?;
+
+    let card_number = card_value1.card_number.as_str();
+    let last4 = card_value1.card_last_four.unwrap_or_else(|| {
+        card_number
+            .get(card_number.len().saturating_sub(4)..)
+            .unwrap_or_default()
+            .to_string()
+    });
+    let card_isin = card_number.get(..6).unwrap_or_default().to_string();
+
+    Ok((
+        CardDisplay {
+            last4,
+            exp_month: card_value1.exp_month.into(),
+            exp_year: card_value1.exp_year.into(),
+            network: None,
+        },
+        card_isin,
+    ))
+}
+
 pub struct Vault;
 
 impl Vault {
+    /// Fetches the details of a vaulted card which are safe to display, such as when resuming a
+    /// checkout, without reconstructing the card out of the token
+    #[instrument(skip_all)]
+    pub async fn get_card_display_data(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<CardDisplay> {
+        let (de_tokenize, _) = get_tokenized_data(
+            state,
+            lookup_key,
+            false,
+            merchant_key_store.key.get_inner(),
+            None,
+            None,
+            Some(merchant_key_store.merchant_id.as_str()),
+        )
+        .await?;
+        let (card_display, card_isin) = get_card_display_from_value1(de_tokenize.value1)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error getting card display data from value1")?;
+
+        let card_info = state
+            .store
+            .get_card_info(&card_isin)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get card info")?;
+
+        Ok(CardDisplay {
+            network: card_info.and_then(|card_info| card_info.card_network),
+            ..card_display
+        })
+    }
+
+    /// Fetches the payment method stored against the token. When an expected payment method is
+    /// given, tokens holding a different payment method fail with
+    /// `VaultError::TokenMethodMismatch`; `None` accepts any payment method.
     #[instrument(skip_all)]
     pub async fn get_payment_method_data_from_locker(
         state: &routes::SessionState,
         lookup_key: &str,
         merchant_key_store: &domain::MerchantKeyStore,
+        expected_payment_method: Option<enums::PaymentMethod>,
     ) -> RouterResult<(Option<api::PaymentMethodData>, SupplementaryVaultData)> {
-        let de_tokenize =
-            get_tokenized_data(state, lookup_key, true, merchant_key_store.key.get_inner()).await?;
-        let (payment_method, customer_id) =
-            api::PaymentMethodData::from_values(de_tokenize.value1, de_tokenize.value2)
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Error parsing Payment Method from Values")?;
+        let (payment_method, supp_data, _) =
+            Self::get_payment_method_with_raw(state, lookup_key, merchant_key_store).await?;
+        verify_token_payment_method(&payment_method, expected_payment_method).change_context(
+            errors::ApiErrorResponse::UnprocessableEntity {
+                message: "Token does not hold the expected payment method".into(),
+            },
+        )?;
+
+        Ok((Some(payment_method), supp_data))
+    }
 
-        Ok((Some(payment_method), customer_id))
+    /// Fetches the payment method stored against the token along with the raw payload it was
+    /// decoded from, for auditing decode mismatches. The raw payload holds the unmasked payment
+    /// method data, so it is returned as a secret and must not be logged.
+    #[instrument(skip_all)]
+    pub async fn get_payment_method_with_raw(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<(
+        api::PaymentMethodData,
+        SupplementaryVaultData,
+        masking::Secret<api::TokenizePayloadRequest>,
+    )> {
+        let (de_tokenize, _, token_version) = get_tokenized_data_with_keys(
+            state,
+            lookup_key,
+            &[merchant_key_store.key.get_inner()],
+            None,
+            None,
+        )
+        .await?;
+        let (payment_method, supp_data) = api::PaymentMethodData::from_values(
+            de_tokenize.value1.clone(),
+            de_tokenize.value2.clone(),
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error parsing Payment Method from Values")?;
+
+        Ok((
+            payment_method,
+            SupplementaryVaultData {
+                token_version,
+                ..supp_data
+            },
+            masking::Secret::new(de_tokenize),
+        ))
     }
 
+    /// Stores the payment method in the temporary locker and returns its token. The token is
+    /// `token_id` when given, for callers that manage token ids themselves. With an
+    /// `idempotency_key` instead, the token is derived from it and an existing token for the key
+    /// is returned as is, which makes retries of the same request safe. Otherwise a new token is
+    /// generated.
     #[instrument(skip_all)]
+    #[allow(clippy::too_many_arguments)]
     pub async fn store_payment_method_data_in_locker(
         state: &routes::SessionState,
         token_id: Option<String>,
+        idempotency_key: Option<&str>,
         payment_method: &api::PaymentMethodData,
         customer_id: Option<id_type::CustomerId>,
         pm: enums::PaymentMethod,
         merchant_key_store: &domain::MerchantKeyStore,
+        schedule_delete_task: bool,
+        billing_address: Option<&api_models::payments::Address>,
+        payment_method_id: Option<String>,
     ) -> RouterResult<String> {
         let value1 = payment_method
             .get_value1(customer_id.clone())
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Error getting Value1 for locker")?;
 
-        let value2 = payment_method
-            .get_value2(customer_id)
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Error getting Value12 for locker")?;
-
-        let lookup_key = token_id.unwrap_or_else(|| generate_id_with_default_len("token"));
+        let store_cvc = should_store_cvc(state, &merchant_key_store.merchant_id).await?;
+        let value2 = get_payment_method_value2(
+            payment_method,
+            customer_id.clone(),
+            store_cvc,
+            billing_address,
+            payment_method_id,
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error getting Value12 for locker")?;
+        warn_if_customer_id_not_stored::<api::PaymentMethodData>(&value2, customer_id.as_ref());
+
+        let lookup_key = match (token_id, idempotency_key) {
+            (Some(_), Some(_)) => Err(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Only one of token_id and idempotency_key can be given")?,
+            (Some(token_id), None) => token_id,
+            (None, Some(idempotency_key)) => VaultKeyKind::PaymentToken
+                .derive_lookup_key(
+                    merchant_key_store.key.get_inner().peek(),
+                    &merchant_key_store.merchant_id,
+                    idempotency_key,
+                )
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to derive lookup key from idempotency key")?,
+            (None, None) => VaultKeyKind::PaymentToken.generate_lookup_key(),
+        };
+        let algorithm =
+            get_temp_locker_encryption_algorithm(state, &merchant_key_store.merchant_id).await?;
 
-        let lookup_key = create_tokenize(
+        let (lookup_key, created) = create_tokenize(
             state,
             value1,
             Some(value2),
             lookup_key,
             merchant_key_store.key.get_inner(),
+            algorithm,
+            None,
         )
         .await?;
-        add_delete_tokenized_data_task(&*state.store, &lookup_key, pm).await?;
-        metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
+        // The token of a retried request was stored, and its delete task scheduled, by the
+        // first attempt
+        if !created && idempotency_key.is_some() {
+            return Ok(lookup_key);
+        }
+        // Short lived tokens can rely on the redis expiry alone, without a process tracker entry
+        if schedule_delete_task {
+            add_delete_tokenized_data_task(
+                &*state.store,
+                &lookup_key,
+                pm,
+                &state.conf.locker.delete_tokenized_data_task_tag,
+            )
+            .await?;
+            metrics::TOKENIZED_DATA_COUNT.add(&metrics::CONTEXT, 1, &[]);
+        } else {
+            metrics::TOKENIZED_DATA_WITHOUT_DELETE_TASK_COUNT.add(&metrics::CONTEXT, 1, &[]);
+        }
         Ok(lookup_key)
     }
 
-    #[cfg(feature = "payouts")]
+    /// Fetches the payment method stored against the token with the keys of the key provider,
+    /// such as `RotatingVaultKeys` holding the current and previous key store keys of the merchant
+    /// during a key store rotation. Tokens read with a legacy key are re-encrypted with the primary
+    /// key.
     #[instrument(skip_all)]
-    pub async fn get_payout_method_data_from_temporary_locker(
+    pub async fn get_payment_method_data_from_locker_with_keys(
         state: &routes::SessionState,
         lookup_key: &str,
-        merchant_key_store: &domain::MerchantKeyStore,
-    ) -> RouterResult<(Option<api::PayoutMethodData>, SupplementaryVaultData)> {
-        let de_tokenize =
-            get_tokenized_data(state, lookup_key, true, merchant_key_store.key.get_inner()).await?;
-        let (payout_method, supp_data) =
-            api::PayoutMethodData::from_values(de_tokenize.value1, de_tokenize.value2)
+        merchant_id: &str,
+        key_provider: &impl VaultKeyProvider,
+    ) -> RouterResult<(Option<api::PaymentMethodData>, SupplementaryVaultData)> {
+        let (de_tokenize, token_version) = get_tokenized_data(
+            state,
+            lookup_key,
+            false,
+            key_provider,
+            None,
+            None,
+            Some(merchant_id),
+        )
+        .await?;
+        let (payment_method, supp_data) =
+            api::PaymentMethodData::from_values(de_tokenize.value1, de_tokenize.value2)
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Error parsing Payout Method from Values")?;
+                .attach_printable("Error parsing Payment Method from Values")?;
 
-        Ok((Some(payout_method), supp_data))
+        Ok((
+            Some(payment_method),
+            SupplementaryVaultData {
+                token_version,
+                ..supp_data
+            },
+        ))
+    }
+
+    /// Replaces the payment method stored against an existing token, keeping its lookup key and
+    /// remaining time to live
+    #[instrument(skip_all)]
+    pub async fn update_payment_method_in_locker(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        payment_method: &api::PaymentMethodData,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<()> {
+        let (_, supp_data, raw_payload) =
+            Self::get_payment_method_with_raw(state, lookup_key, merchant_key_store).await?;
+
+        let value1 = payment_method
+            .get_value1(supp_data.customer_id.clone())
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error getting Value1 for locker")?;
+
+        let store_cvc = should_store_cvc(state, &merchant_key_store.merchant_id).await?;
+        let value2 = get_replaced_payment_method_value2(payment_method, supp_data, store_cvc)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error getting Value2 for locker")?;
+        let algorithm =
+            get_temp_locker_encryption_algorithm(state, &merchant_key_store.merchant_id).await?;
+
+        update_tokenize(
+            state,
+            value1,
+            Some(value2),
+            lookup_key.to_string(),
+            raw_payload.peek().created_at,
+            merchant_key_store.key.get_inner(),
+            algorithm,
+        )
+        .await
+    }
+
+    #[cfg(feature = "payouts")]
+    #[instrument(skip_all)]
+    pub async fn get_payout_method_data_from_temporary_locker(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<(Option<api::PayoutMethodData>, SupplementaryVaultData)> {
+        let (de_tokenize, _, token_version) = get_tokenized_data_with_keys(
+            state,
+            lookup_key,
+            &[merchant_key_store.key.get_inner()],
+            None,
+            None,
+        )
+        .await?;
+        let (payout_method, supp_data) =
+            api::PayoutMethodData::from_values(de_tokenize.value1, de_tokenize.value2)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Error parsing Payout Method from Values")?;
+
+        Ok((
+            Some(payout_method),
+            SupplementaryVaultData {
+                token_version,
+                ..supp_data
+            },
+        ))
     }
 
     #[cfg(feature = "payouts")]
@@ -892,25 +1913,42 @@ impl Vault {
         customer_id: Option<id_type::CustomerId>,
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> RouterResult<String> {
+        if let api::PayoutMethodData::Wallet(wallet) = payout_method {
+            if let Some(allowed_wallet_types) =
+                get_allowed_payout_wallet_types(state, &merchant_key_store.merchant_id).await?
+            {
+                validate_wallet_payout_type(wallet, &allowed_wallet_types).change_context(
+                    errors::ApiErrorResponse::NotSupported {
+                        message: "Payout wallet type is not enabled for this merchant".to_string(),
+                    },
+                )?;
+            }
+        }
+
         let value1 = payout_method
             .get_value1(customer_id.clone())
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Error getting Value1 for locker")?;
 
         let value2 = payout_method
-            .get_value2(customer_id)
+            .get_value2(customer_id.clone())
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Error getting Value2 for locker")?;
+        warn_if_customer_id_not_stored::<api::PayoutMethodData>(&value2, customer_id.as_ref());
 
         let lookup_key =
-            token_id.unwrap_or_else(|| generate_id_with_default_len("temporary_token"));
+            token_id.unwrap_or_else(|| VaultKeyKind::PayoutToken.generate_lookup_key());
+        let algorithm =
+            get_temp_locker_encryption_algorithm(state, &merchant_key_store.merchant_id).await?;
 
-        let lookup_key = create_tokenize(
+        let (lookup_key, _) = create_tokenize(
             state,
             value1,
             Some(value2),
             lookup_key,
             merchant_key_store.key.get_inner(),
+            algorithm,
+            None,
         )
         .await?;
         // add_delete_tokenized_data_task(&*state.store, &lookup_key, pm).await?;
@@ -918,19 +1956,382 @@ impl Vault {
         Ok(lookup_key)
     }
 
+    /// Micro-deposit verification status of the payout bank account stored against the token.
+    /// Accounts of which the status was never set are unverified.
+    #[cfg(feature = "payouts")]
+    #[instrument(skip_all)]
+    pub async fn get_payout_bank_verification_status(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<BankVerificationStatus> {
+        let (de_tokenize, _, _) = get_tokenized_data_with_keys(
+            state,
+            lookup_key,
+            &[merchant_key_store.key.get_inner()],
+            None,
+            None,
+        )
+        .await?;
+
+        get_bank_verification_status(de_tokenize.value2).change_context(
+            errors::ApiErrorResponse::UnprocessableEntity {
+                message: "Token does not hold a bank account".into(),
+            },
+        )
+    }
+
+    /// Records the outcome of the micro-deposit verification of the ACH or SEPA account stored
+    /// against the token, keeping its lookup key and remaining time to live. This is the only way
+    /// the verification status of a vaulted account is set, as payout requests cannot carry it.
+    #[cfg(feature = "payouts")]
+    #[instrument(skip_all)]
+    pub async fn set_payout_bank_verification_status(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        verification_status: BankVerificationStatus,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<()> {
+        let (de_tokenize, _, _) = get_tokenized_data_with_keys(
+            state,
+            lookup_key,
+            &[merchant_key_store.key.get_inner()],
+            None,
+            None,
+        )
+        .await?;
+        let (payout_method, _) = api::PayoutMethodData::from_values(
+            de_tokenize.value1.clone(),
+            de_tokenize.value2.clone(),
+        )
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error parsing Payout Method from Values")?;
+        if !matches!(
+            payout_method,
+            api::PayoutMethodData::Bank(api::BankPayout::Ach(_) | api::BankPayout::Sepa(_))
+        ) {
+            Err(errors::ApiErrorResponse::UnprocessableEntity {
+                message: "Only ACH and SEPA bank accounts are verified through micro-deposits"
+                    .into(),
+            })?;
+        }
+
+        let value2 = set_bank_verification_status(de_tokenize.value2, verification_status)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error setting the verification status in Value2")?;
+        let algorithm =
+            get_temp_locker_encryption_algorithm(state, &merchant_key_store.merchant_id).await?;
+
+        update_tokenize(
+            state,
+            de_tokenize.value1,
+            Some(value2),
+            lookup_key.to_string(),
+            de_tokenize.created_at,
+            merchant_key_store.key.get_inner(),
+            algorithm,
+        )
+        .await
+    }
+
+    /// Returns the remaining lifetime of a temporary locker token in seconds, or `None` if the
+    /// token does not exist or has no expiry.
+    #[instrument(skip_all)]
+    pub async fn get_token_ttl(
+        state: &routes::SessionState,
+        lookup_key: &str,
+    ) -> RouterResult<Option<i64>> {
+        let redis_key = get_redis_locker_key(lookup_key);
+        let redis_conn = get_vault_redis_conn(state)?;
+
+        let ttl = redis_conn
+            .get_ttl(redis_key.as_str())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get ttl of token from redis locker")?;
+
+        Ok((ttl >= 0).then_some(ttl))
+    }
+
+    /// Checks whether a temporary locker token is still alive, without fetching its payload
+    pub async fn token_exists(
+        state: &routes::SessionState,
+        lookup_key: &str,
+    ) -> RouterResult<bool> {
+        let redis_key = get_redis_locker_key(lookup_key);
+        get_vault_redis_conn(state)?
+            .exists::<()>(redis_key.as_str())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to check if token exists in redis locker")
+    }
+
+    /// Fetches the payment method stored against the token and extends the token's time to live
+    /// to `extend_by` seconds from now, never beyond `locker.temp_locker_max_lifetime_in_secs`
+    /// after the token was created. The time to live is extended only if the token still holds
+    /// the payload that was read, so a token replaced or deleted in between is left as is. Tokens
+    /// created before their creation time was recorded are not extended.
+    #[instrument(skip_all)]
+    pub async fn get_and_refresh(
+        state: &routes::SessionState,
+        lookup_key: &str,
+        extend_by: i64,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<(Option<api::PaymentMethodData>, SupplementaryVaultData)> {
+        let redis_key = get_redis_locker_key(lookup_key);
+        let redis_conn = get_vault_redis_conn(state)?;
+        let payload = get_locker_payload(&redis_conn, redis_key.as_str())
+            .await
+            .change_context(errors::ApiErrorResponse::UnprocessableEntity {
+                message: "Token is invalid or expired".into(),
+            })?;
+
+        let (de_tokenize, _, token_version) = decode_locker_payload(
+            state,
+            &payload,
+            &[merchant_key_store.key.get_inner()],
+            get_temp_locker_value1_key(state)?.as_ref(),
+            None,
+        )?;
+        let (payment_method, supp_data) =
+            api::PaymentMethodData::from_values(de_tokenize.value1, de_tokenize.value2)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Error parsing Payment Method from Values")?;
+
+        if let Some(created_at) = de_tokenize.created_at {
+            let deadline =
+                created_at.saturating_add(state.conf.locker.temp_locker_max_lifetime_in_secs);
+            let extended = redis_conn
+                .evaluate_redis_script::<_, i64>(
+                    EXTEND_TOKEN_TTL_SCRIPT,
+                    vec![redis_key],
+                    vec![
+                        payload,
+                        bytes::Bytes::from(extend_by.to_string()),
+                        bytes::Bytes::from(deadline.to_string()),
+                    ],
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to extend ttl of token in redis locker")?;
+            if extended == 0 {
+                logger::warn!("Token was replaced while being read, its ttl was not extended");
+            }
+        }
+
+        Ok((
+            Some(payment_method),
+            SupplementaryVaultData {
+                token_version,
+                ..supp_data
+            },
+        ))
+    }
+
     #[instrument(skip_all)]
     pub async fn delete_locker_payment_method_by_lookup_key(
         state: &routes::SessionState,
         lookup_key: &Option<String>,
     ) {
         if let Some(lookup_key) = lookup_key {
-            delete_tokenized_data(state, lookup_key)
+            delete_tokenized_data(state, lookup_key, None)
                 .await
                 .map(|_| logger::info!("Card From locker deleted Successfully"))
                 .map_err(|err| logger::error!("Error: Deleting Card From Redis Locker : {:?}", err))
                 .ok();
         }
     }
+
+    /// Deletes all the tokens of the merchant from the temporary locker, when offboarding the
+    /// merchant. The tokens are found by decrypting every payload in the locker with the key of
+    /// the merchant and checking that it belongs to the current tenant, which makes this an
+    /// expensive operation. Returns the number of tokens deleted.
+    #[instrument(skip_all)]
+    pub async fn purge_merchant_tokens(
+        state: &routes::SessionState,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<usize> {
+        let value1_encryption_key = get_temp_locker_value1_key(state)?;
+        let redis_conn = get_vault_redis_conn(state)?;
+        let redis_keys = redis_conn
+            .scan_keys(
+                &get_redis_locker_key("*"),
+                Some(TEMP_LOCKER_PURGE_SCAN_COUNT),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to scan redis locker")?;
+
+        let mut scanned_count = 0;
+        let mut purged_count = 0;
+        for redis_keys_batch in redis_keys.chunks(TEMP_LOCKER_PURGE_BATCH_SIZE) {
+            let lookup_keys = get_merchant_token_payloads(
+                state,
+                &redis_conn,
+                redis_keys_batch,
+                merchant_key_store,
+                value1_encryption_key.as_ref(),
+            )
+            .await?
+            .into_iter()
+            .map(|payload| payload.lookup_key)
+            .collect::<Vec<_>>();
+
+            let batch_purged_count = delete_tokenized_data_batch(state, lookup_keys)
+                .await?
+                .into_iter()
+                .filter(|(_, deleted)| *deleted)
+                .count();
+            scanned_count += redis_keys_batch.len();
+            purged_count += batch_purged_count;
+
+            metrics::TEMP_LOCKER_PURGE_SCANNED_TOKENS.add(
+                &metrics::CONTEXT,
+                u64::try_from(redis_keys_batch.len()).unwrap_or(u64::MAX),
+                &[],
+            );
+            metrics::TEMP_LOCKER_PURGED_TOKENS.add(
+                &metrics::CONTEXT,
+                u64::try_from(batch_purged_count).unwrap_or(u64::MAX),
+                &[],
+            );
+            logger::info!(
+                "Purged {purged_count} tokens of merchant {} after scanning {scanned_count} of {} tokens",
+                merchant_key_store.merchant_id,
+                redis_keys.len()
+            );
+        }
+
+        Ok(purged_count)
+    }
+
+    /// Counts the tokens of the merchant in the temporary locker by the kind of payment method
+    /// they hold. The gauges report the latest counts of all the counted merchants summed by
+    /// payment method, so that their cardinality does not grow with the number of merchants.
+    /// Like purging, this decrypts every payload in the locker, so a merchant can only be
+    /// counted once every `TEMP_LOCKER_TOKEN_COUNT_INTERVAL`; it is meant for periodic reporting.
+    #[instrument(skip_all)]
+    pub async fn token_counts_by_type(
+        state: &routes::SessionState,
+        merchant_key_store: &domain::MerchantKeyStore,
+    ) -> RouterResult<HashMap<VaultPaymentMethodKind, usize>> {
+        {
+            let mut counted_at = TEMP_LOCKER_TOKEN_COUNTED_AT
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let now = std::time::Instant::now();
+            if counted_at
+                .get(&merchant_key_store.merchant_id)
+                .is_some_and(|last| now.duration_since(*last) < TEMP_LOCKER_TOKEN_COUNT_INTERVAL)
+            {
+                return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                    message: format!(
+                        "Tokens of a merchant can be counted once every {} seconds",
+                        TEMP_LOCKER_TOKEN_COUNT_INTERVAL.as_secs()
+                    ),
+                }));
+            }
+            counted_at.insert(merchant_key_store.merchant_id.clone(), now);
+        }
+
+        let value1_encryption_key = get_temp_locker_value1_key(state)?;
+        let redis_conn = get_vault_redis_conn(state)?;
+        let redis_keys = redis_conn
+            .scan_keys(
+                &get_redis_locker_key("*"),
+                Some(TEMP_LOCKER_PURGE_SCAN_COUNT),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to scan redis locker")?;
+
+        let mut token_counts = HashMap::new();
+        for redis_keys_batch in redis_keys.chunks(TEMP_LOCKER_PURGE_BATCH_SIZE) {
+            get_merchant_token_payloads(
+                state,
+                &redis_conn,
+                redis_keys_batch,
+                merchant_key_store,
+                value1_encryption_key.as_ref(),
+            )
+            .await?
+            .iter()
+            .filter_map(|payload| VaultPaymentMethodKind::from_value1(&payload.value1))
+            .for_each(|kind| *token_counts.entry(kind).or_insert(0) += 1);
+        }
+
+        let total_token_counts = {
+            let mut merchant_token_counts = TEMP_LOCKER_TOKEN_COUNTS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            merchant_token_counts
+                .insert(merchant_key_store.merchant_id.clone(), token_counts.clone());
+            merchant_token_counts.values().flatten().fold(
+                HashMap::new(),
+                |mut total_token_counts, (kind, count)| {
+                    *total_token_counts.entry(*kind).or_insert(0) += count;
+                    total_token_counts
+                },
+            )
+        };
+        for kind in <VaultPaymentMethodKind as strum::IntoEnumIterator>::iter() {
+            metrics::TEMP_LOCKER_TOKEN_COUNT.observe(
+                &metrics::CONTEXT,
+                u64::try_from(total_token_counts.get(&kind).copied().unwrap_or(0))
+                    .unwrap_or(u64::MAX),
+                &[metrics::request::add_attributes(
+                    "payment_method",
+                    kind.to_string(),
+                )],
+            );
+        }
+        logger::info!(
+            "Counted tokens of merchant {} after scanning {} tokens: {token_counts:?}",
+            merchant_key_store.merchant_id,
+            redis_keys.len()
+        );
+
+        Ok(token_counts)
+    }
+}
+
+/// Fetches the given locker keys and returns the payloads among them that belong to the merchant
+/// and the current tenant. Payloads that fail to decrypt belong to other merchants, and payloads
+/// missing by now have expired since the keys were scanned. The batch of reads holds a single
+/// in-flight operation permit, like a batch of deletes.
+async fn get_merchant_token_payloads(
+    state: &routes::SessionState,
+    redis_conn: &redis_interface::RedisConnectionPool,
+    redis_keys: &[String],
+    merchant_key_store: &domain::MerchantKeyStore,
+    value1_encryption_key: Option<&masking::Secret<Vec<u8>>>,
+) -> RouterResult<Vec<api::TokenizePayloadRequest>> {
+    let _permit = acquire_vault_operation_permit(state)?;
+    let payloads = futures::future::join_all(
+        redis_keys
+            .iter()
+            .map(|redis_key| redis_conn.get_key::<bytes::Bytes>(redis_key)),
+    )
+    .await
+    .into_iter()
+    .filter_map(Result::ok)
+    .filter_map(|payload| {
+        decrypt_tokenize_payload_with_keys(
+            &payload,
+            &[merchant_key_store.key.get_inner()],
+            value1_encryption_key,
+        )
+        .ok()
+    })
+    .filter_map(|(decrypted_payload, _, _)| {
+        bytes::Bytes::from(decrypted_payload)
+            .parse_struct::<api::TokenizePayloadRequest>("TokenizePayloadRequest")
+            .ok()
+    })
+    .filter(|payload| verify_token_tenant(payload, &state.tenant.name).is_ok())
+    .collect();
+
+    Ok(payloads)
 }
 
 //------------------------------------------------TokenizeService------------------------------------------------
@@ -940,48 +2341,655 @@ fn get_redis_locker_key(lookup_key: &str) -> String {
     format!("{}_{}", consts::LOCKER_REDIS_PREFIX, lookup_key)
 }
 
-#[instrument(skip(state, value1, value2))]
-pub async fn create_tokenize(
+/// Consecutive redis failures after which the circuit is opened
+const VAULT_CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+/// Time for which the circuit stays open before a probe request is let through
+const VAULT_CIRCUIT_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Stops calls to the redis locker from waiting on an unavailable redis. The circuit opens after
+/// `VAULT_CIRCUIT_FAILURE_THRESHOLD` consecutive failures of redis connections or commands, after
+/// which calls fail immediately until the cooldown elapses and a single probe request is let
+/// through. The outcome of the probe's redis command decides whether the circuit closes.
+struct VaultCircuitBreaker {
+    consecutive_failures: std::sync::atomic::AtomicU32,
+    opened_at: std::sync::Mutex<Option<std::time::Instant>>,
+}
+
+static VAULT_CIRCUIT_BREAKER: VaultCircuitBreaker = VaultCircuitBreaker {
+    consecutive_failures: std::sync::atomic::AtomicU32::new(0),
+    opened_at: std::sync::Mutex::new(None),
+};
+
+impl VaultCircuitBreaker {
+    fn check(&self) -> CustomResult<(), errors::VaultError> {
+        let mut opened_at = self
+            .opened_at
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match *opened_at {
+            Some(opened) if opened.elapsed() < VAULT_CIRCUIT_COOLDOWN => {
+                Err(report!(errors::VaultError::BackendUnavailable))
+            }
+            Some(_) => {
+                // Half open, let this request probe redis while the others keep failing fast
+                *opened_at = Some(std::time::Instant::now());
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn record_success(&self) {
+        self.consecutive_failures
+            .store(0, std::sync::atomic::Ordering::SeqCst);
+        *self
+            .opened_at
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+    }
+
+    /// Records the outcome of a redis command. Commands that did not find their key still got a
+    /// response from redis, so only other errors count as failures.
+    fn record_outcome<T>(&self, result: &CustomResult<T, redis_interface::errors::RedisError>) {
+        match result {
+            Ok(_) => self.record_success(),
+            Err(err)
+                if matches!(
+                    err.current_context(),
+                    redis_interface::errors::RedisError::NotFound
+                ) =>
+            {
+                self.record_success()
+            }
+            Err(_) => self.record_failure(),
+        }
+    }
+
+    fn record_failure(&self) {
+        let failures = self
+            .consecutive_failures
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            .saturating_add(1);
+        if failures >= VAULT_CIRCUIT_FAILURE_THRESHOLD {
+            let mut opened_at = self
+                .opened_at
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if opened_at.is_none() {
+                metrics::VAULT_CIRCUIT_OPEN.add(&metrics::CONTEXT, 1, &[]);
+            }
+            *opened_at = Some(std::time::Instant::now());
+        }
+    }
+}
+
+/// Bounds the redis locker operations in flight, so that callers beyond the limit fail immediately
+/// with `VaultError::BackendBusy` instead of piling up on a slow redis
+struct VaultOperationLimiter {
+    semaphore: tokio::sync::Semaphore,
+    max_in_flight: usize,
+}
+
+/// Initialized from the locker settings on the first redis locker operation
+static VAULT_OPERATION_LIMITER: std::sync::OnceLock<VaultOperationLimiter> =
+    std::sync::OnceLock::new();
+
+/// Held for the duration of a redis locker operation
+struct VaultOperationPermit {
+    limiter: &'static VaultOperationLimiter,
+    _permit: tokio::sync::SemaphorePermit<'static>,
+}
+
+impl VaultOperationLimiter {
+    fn new(max_in_flight: usize) -> Self {
+        let max_in_flight = max_in_flight.min(tokio::sync::Semaphore::MAX_PERMITS);
+        Self {
+            semaphore: tokio::sync::Semaphore::new(max_in_flight),
+            max_in_flight,
+        }
+    }
+
+    fn try_acquire(&'static self) -> CustomResult<VaultOperationPermit, errors::VaultError> {
+        let permit = self
+            .semaphore
+            .try_acquire()
+            .change_context(errors::VaultError::BackendBusy)?;
+        self.report_in_flight(self.in_flight());
+        Ok(VaultOperationPermit {
+            limiter: self,
+            _permit: permit,
+        })
+    }
+
+    fn in_flight(&self) -> usize {
+        self.max_in_flight
+            .saturating_sub(self.semaphore.available_permits())
+    }
+
+    fn report_in_flight(&self, in_flight: usize) {
+        metrics::TEMP_LOCKER_IN_FLIGHT_OPERATIONS.observe(
+            &metrics::CONTEXT,
+            u64::try_from(in_flight).unwrap_or(u64::MAX),
+            &[],
+        );
+    }
+}
+
+impl Drop for VaultOperationPermit {
+    fn drop(&mut self) {
+        // The semaphore permit is only released after this runs
+        self.limiter
+            .report_in_flight(self.limiter.in_flight().saturating_sub(1));
+    }
+}
+
+fn acquire_vault_operation_permit(
+    state: &routes::SessionState,
+) -> RouterResult<VaultOperationPermit> {
+    VAULT_OPERATION_LIMITER
+        .get_or_init(|| VaultOperationLimiter::new(state.conf.locker.max_in_flight_operations))
+        .try_acquire()
+        .map_err(|err| {
+            metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+            err
+        })
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Too many redis locker operations in flight")
+}
+
+fn get_vault_redis_conn(
     state: &routes::SessionState,
+) -> RouterResult<std::sync::Arc<redis_interface::RedisConnectionPool>> {
+    VAULT_CIRCUIT_BREAKER
+        .check()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Redis locker circuit is open")?;
+
+    state.store.get_redis_conn().map_err(|err| {
+        if matches!(
+            err.current_context(),
+            redis_interface::errors::RedisError::RedisConnectionError
+        ) {
+            VAULT_CIRCUIT_BREAKER.record_failure();
+        }
+        err.change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to get redis connection")
+    })
+}
+
+/// Supplies the keys temporary locker payloads are encrypted with. Payloads are always encrypted
+/// with the primary key, while payloads encrypted with any of the legacy keys remain readable, so
+/// that keys can be rotated without failing reads of tokens created before the rotation.
+pub trait VaultKeyProvider {
+    fn primary_key(&self) -> &masking::Secret<Vec<u8>>;
+
+    /// Keys that payloads may still be encrypted with, in the order they are tried
+    fn legacy_keys(&self) -> &[masking::Secret<Vec<u8>>];
+
+    /// The primary key followed by the legacy keys
+    fn encryption_keys(&self) -> Vec<&masking::Secret<Vec<u8>>> {
+        std::iter::once(self.primary_key())
+            .chain(self.legacy_keys())
+            .collect()
+    }
+}
+
+impl VaultKeyProvider for masking::Secret<Vec<u8>> {
+    fn primary_key(&self) -> &masking::Secret<Vec<u8>> {
+        self
+    }
+
+    fn legacy_keys(&self) -> &[masking::Secret<Vec<u8>>] {
+        &[]
+    }
+}
+
+/// Keys of a vault encryption key rotation in progress, accepting payloads encrypted with the
+/// legacy keys until they are re-encrypted with the primary key on read
+#[derive(Clone)]
+pub struct RotatingVaultKeys {
+    primary_key: masking::Secret<Vec<u8>>,
+    legacy_keys: Vec<masking::Secret<Vec<u8>>>,
+}
+
+impl RotatingVaultKeys {
+    pub fn new(
+        primary_key: masking::Secret<Vec<u8>>,
+        legacy_keys: Vec<masking::Secret<Vec<u8>>>,
+    ) -> Self {
+        Self {
+            primary_key,
+            legacy_keys,
+        }
+    }
+}
+
+impl VaultKeyProvider for RotatingVaultKeys {
+    fn primary_key(&self) -> &masking::Secret<Vec<u8>> {
+        &self.primary_key
+    }
+
+    fn legacy_keys(&self) -> &[masking::Secret<Vec<u8>>] {
+        &self.legacy_keys
+    }
+}
+
+fn encrypt_tokenize_payload(
     value1: String,
     value2: Option<String>,
     lookup_key: String,
+    tenant_id: String,
+    created_at: Option<i64>,
+    algorithm: TempLockerEncryptionAlgorithm,
     encryption_key: &masking::Secret<Vec<u8>>,
-) -> RouterResult<String> {
-    let redis_key = get_redis_locker_key(lookup_key.as_str());
-    let func = || async {
-        metrics::CREATED_TOKENIZED_CARD.add(&metrics::CONTEXT, 1, &[]);
+) -> RouterResult<Vec<u8>> {
+    let payload_to_be_encrypted = api::TokenizePayloadRequest {
+        value1,
+        value2: value2.unwrap_or_default(),
+        lookup_key,
+        service_name: VAULT_SERVICE_NAME.to_string(),
+        tenant_id: Some(tenant_id),
+        created_at,
+    };
 
-        let payload_to_be_encrypted = api::TokenizePayloadRequest {
-            value1: value1.clone(),
-            value2: value2.clone().unwrap_or_default(),
-            lookup_key: lookup_key.clone(),
-            service_name: VAULT_SERVICE_NAME.to_string(),
-        };
+    let payload = payload_to_be_encrypted
+        .encode_to_string_of_json()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
-        let payload = payload_to_be_encrypted
-            .encode_to_string_of_json()
-            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let encrypted_payload =
+        encrypt_with_algorithm_header(payload.as_bytes(), algorithm, encryption_key)?;
 
-        let encrypted_payload = GcmAes256
-            .encode_message(encryption_key.peek().as_ref(), payload.as_bytes())
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed to encode redis temp locker data")?;
+    Ok(std::iter::once(TEMP_LOCKER_KEY_VERSION)
+        .chain(encrypted_payload)
+        .collect())
+}
 
-        let redis_conn = state
-            .store
-            .get_redis_conn()
+/// Encrypts the data with the algorithm, prefixed with the header byte identifying the algorithm
+fn encrypt_with_algorithm_header(
+    data: &[u8],
+    algorithm: TempLockerEncryptionAlgorithm,
+    encryption_key: &masking::Secret<Vec<u8>>,
+) -> RouterResult<Vec<u8>> {
+    let encryption_key = encryption_key.peek().as_ref();
+    let (algorithm_header, encrypted_data) = match algorithm {
+        TempLockerEncryptionAlgorithm::GcmAes256 => (
+            TEMP_LOCKER_GCM_AES_256_HEADER,
+            GcmAes256.encode_message(encryption_key, data),
+        ),
+        TempLockerEncryptionAlgorithm::ChaCha20Poly1305 => (
+            TEMP_LOCKER_CHACHA20_POLY1305_HEADER,
+            ChaCha20Poly1305.encode_message(encryption_key, data),
+        ),
+    };
+    let encrypted_data = encrypted_data
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode redis temp locker data")?;
+
+    Ok(std::iter::once(algorithm_header)
+        .chain(encrypted_data)
+        .collect())
+}
+
+/// Encrypts value1 with its own key, apart from the rest of the payload which is encrypted with
+/// the encryption key. The value1 segment is prefixed with its length, so that the payload is
+/// `[key version, split values header, value1 segment length, value1 segment, value2 segment]`.
+///
+/// Both segments are stored in a single redis value rather than as two fields of a redis hash.
+/// Creating a token with its expiry only if it does not exist, replacing it while keeping its time
+/// to live, moving it under the tenant prefix and extending it only if unchanged each rely on the
+/// token being a single string value, and a hash would need separate commands for each of them.
+/// The key separation does not depend on the layout, as each segment is encrypted with its own key.
+#[allow(clippy::too_many_arguments)]
+fn encrypt_split_tokenize_payload(
+    value1: String,
+    value2: Option<String>,
+    lookup_key: String,
+    tenant_id: String,
+    created_at: Option<i64>,
+    algorithm: TempLockerEncryptionAlgorithm,
+    value1_encryption_key: &masking::Secret<Vec<u8>>,
+    encryption_key: &masking::Secret<Vec<u8>>,
+) -> RouterResult<Vec<u8>> {
+    let value1_segment =
+        encrypt_with_algorithm_header(value1.as_bytes(), algorithm, value1_encryption_key)?;
+    let value1_segment_length = u32::try_from(value1_segment.len())
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Encrypted value1 is too long for the redis temp locker")?;
+
+    let payload_to_be_encrypted = api::TokenizePayloadRequest {
+        value1: String::new(),
+        value2: value2.unwrap_or_default(),
+        lookup_key,
+        service_name: VAULT_SERVICE_NAME.to_string(),
+        tenant_id: Some(tenant_id),
+        created_at,
+    };
+    let payload = payload_to_be_encrypted
+        .encode_to_string_of_json()
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let value2_segment =
+        encrypt_with_algorithm_header(payload.as_bytes(), algorithm, encryption_key)?;
+
+    Ok([TEMP_LOCKER_KEY_VERSION, TEMP_LOCKER_SPLIT_VALUES_HEADER]
+        .into_iter()
+        .chain(value1_segment_length.to_be_bytes())
+        .chain(value1_segment)
+        .chain(value2_segment)
+        .collect())
+}
+
+/// Key that value1 of temporary locker payloads is encrypted with when it is encrypted apart from
+/// value2, if one is configured
+fn get_temp_locker_value1_key(
+    state: &routes::SessionState,
+) -> RouterResult<Option<masking::Secret<Vec<u8>>>> {
+    let value1_encryption_key = state
+        .conf
+        .jwekey
+        .get_inner()
+        .temp_locker_value1_encryption_key
+        .peek();
+    if value1_encryption_key.is_empty() {
+        return Ok(None);
+    }
+
+    hex::decode(value1_encryption_key)
+        .map(|key| Some(masking::Secret::new(key)))
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to decode the temp locker value1 encryption key")
+}
+
+/// Encrypts a temporary locker payload, encrypting value1 with its own key when the locker is
+/// configured to encrypt value1 and value2 separately
+fn encrypt_locker_payload(
+    state: &routes::SessionState,
+    value1: String,
+    value2: Option<String>,
+    lookup_key: String,
+    created_at: Option<i64>,
+    encryption_key: &masking::Secret<Vec<u8>>,
+    algorithm: TempLockerEncryptionAlgorithm,
+) -> RouterResult<Vec<u8>> {
+    if !state.conf.locker.temp_locker_split_values {
+        return encrypt_tokenize_payload(
+            value1,
+            value2,
+            lookup_key,
+            state.tenant.name.clone(),
+            created_at,
+            algorithm,
+            encryption_key,
+        );
+    }
+
+    let value1_encryption_key = get_temp_locker_value1_key(state)?
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable(
+            "Temp locker value1 encryption key is required to encrypt value1 separately",
+        )?;
+    encrypt_split_tokenize_payload(
+        value1,
+        value2,
+        lookup_key,
+        state.tenant.name.clone(),
+        created_at,
+        algorithm,
+        &value1_encryption_key,
+        encryption_key,
+    )
+}
+
+/// Decrypts a temporary locker payload, without its key version, with the algorithm identified by
+/// its header byte. Payloads stored before the header was introduced are GCM-AES-256 ciphertexts,
+/// which are tried when the header is missing or fails to decrypt.
+fn decode_with_algorithm_header(
+    encryption_key: &masking::Secret<Vec<u8>>,
+    encrypted_payload: &[u8],
+) -> Option<Vec<u8>> {
+    let encryption_key = encryption_key.peek().as_ref();
+    encrypted_payload
+        .split_first()
+        .and_then(|(algorithm_header, ciphertext)| {
+            let ciphertext = masking::Secret::new(ciphertext.to_vec());
+            match *algorithm_header {
+                TEMP_LOCKER_GCM_AES_256_HEADER => {
+                    GcmAes256.decode_message(encryption_key, ciphertext).ok()
+                }
+                TEMP_LOCKER_CHACHA20_POLY1305_HEADER => ChaCha20Poly1305
+                    .decode_message(encryption_key, ciphertext)
+                    .ok(),
+                _ => None,
+            }
+        })
+        .or_else(|| {
+            GcmAes256
+                .decode_message(
+                    encryption_key,
+                    masking::Secret::new(encrypted_payload.to_vec()),
+                )
+                .ok()
+        })
+}
+
+fn get_temp_locker_key_for_version(
+    version: u8,
+    encryption_key: &masking::Secret<Vec<u8>>,
+) -> Option<&masking::Secret<Vec<u8>>> {
+    match version {
+        TEMP_LOCKER_KEY_VERSION => Some(encryption_key),
+        _ => None,
+    }
+}
+
+/// Checks that the token was created for the tenant reading it, as defense in depth against
+/// lookup keys colliding across tenants sharing redis. Untagged tokens belong to the default tenant.
+fn verify_token_tenant(
+    payload: &api::TokenizePayloadRequest,
+    tenant_id: &str,
+) -> CustomResult<(), errors::VaultError> {
+    let token_tenant_id = payload
+        .tenant_id
+        .as_deref()
+        .unwrap_or(common_utils::consts::DEFAULT_TENANT);
+    if token_tenant_id == tenant_id {
+        Ok(())
+    } else {
+        Err(report!(errors::VaultError::TenantMismatch)).attach_printable(format!(
+            "Token created for tenant {token_tenant_id} was read by tenant {tenant_id}"
+        ))
+    }
+}
+
+/// Checks that the token was stored by the service the reader expects, if it expects one
+fn verify_token_service_name(
+    payload: &api::TokenizePayloadRequest,
+    expected_service_name: Option<&str>,
+) -> CustomResult<(), errors::VaultError> {
+    match expected_service_name {
+        Some(expected_service_name) if payload.service_name != expected_service_name => {
+            Err(report!(errors::VaultError::ServiceNameMismatch)).attach_printable(format!(
+                "Token stored by service {} was read expecting service {expected_service_name}",
+                payload.service_name
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Decrypts a temporary locker payload with the key of the version it is prefixed with. Payloads
+/// stored before versioning was introduced are decrypted with the legacy key. Returns the
+/// decrypted payload along with the version it was stored with, or
+/// `VaultError::DecryptionFailed` with the version the payload is prefixed with.
+fn decrypt_tokenize_payload(
+    payload: Vec<u8>,
+    encryption_key: &masking::Secret<Vec<u8>>,
+) -> CustomResult<(Vec<u8>, u8), errors::VaultError> {
+    let key_version = payload.first().copied();
+    let versioned_payload = payload
+        .split_first()
+        .and_then(|(version, encrypted_payload)| {
+            get_temp_locker_key_for_version(*version, encryption_key)
+                .and_then(|key| decode_with_algorithm_header(key, encrypted_payload))
+                .map(|decrypted_payload| (decrypted_payload, *version))
+        });
+
+    match versioned_payload {
+        Some(versioned_payload) => Ok(versioned_payload),
+        None => GcmAes256
+            .decode_message(
+                encryption_key.peek().as_ref(),
+                masking::Secret::new(payload),
+            )
+            .map(|decrypted_payload| (decrypted_payload, LEGACY_TOKEN_VERSION))
+            .change_context(errors::VaultError::DecryptionFailed { key_version })
+            .attach_printable("Failed to decode redis temp locker data"),
+    }
+}
+
+/// Decrypts a temporary locker payload whose value1 and value2 are encrypted separately, returning
+/// the decrypted payload with value1 restored along with the index of the key value2 was
+/// decrypted with. Returns `None` for payloads which are not split or fail to decrypt.
+fn decrypt_split_tokenize_payload(
+    payload: &[u8],
+    value1_encryption_key: &masking::Secret<Vec<u8>>,
+    encryption_keys: &[&masking::Secret<Vec<u8>>],
+) -> Option<(Vec<u8>, usize)> {
+    let (value1_segment, value2_segment) = match payload {
+        [_, TEMP_LOCKER_SPLIT_VALUES_HEADER, l0, l1, l2, l3, segments @ ..] => {
+            let length = usize::try_from(u32::from_be_bytes([*l0, *l1, *l2, *l3])).ok()?;
+            (length <= segments.len()).then(|| segments.split_at(length))?
+        }
+        _ => return None,
+    };
+
+    let value1 = decode_with_algorithm_header(value1_encryption_key, value1_segment)
+        .and_then(|value1| String::from_utf8(value1).ok())?;
+    let (value2_payload, key_index) =
+        encryption_keys
+            .iter()
+            .enumerate()
+            .find_map(|(key_index, encryption_key)| {
+                decode_with_algorithm_header(encryption_key, value2_segment)
+                    .map(|value2_payload| (value2_payload, key_index))
+            })?;
+    let tokenize_payload = bytes::Bytes::from(value2_payload)
+        .parse_struct::<api::TokenizePayloadRequest>("TokenizePayloadRequest")
+        .ok()?;
+
+    api::TokenizePayloadRequest {
+        value1,
+        ..tokenize_payload
+    }
+    .encode_to_vec()
+    .ok()
+    .map(|decrypted_payload| (decrypted_payload, key_index))
+}
+
+/// Decrypts a temporary locker payload with the first of `encryption_keys` that succeeds, using
+/// the value1 encryption key, if any, for payloads whose value1 is encrypted separately.
+/// Returns the decrypted payload along with the version it was stored with, and the index of the
+/// key that was used.
+fn decrypt_tokenize_payload_with_keys(
+    payload: &[u8],
+    encryption_keys: &[&masking::Secret<Vec<u8>>],
+    value1_encryption_key: Option<&masking::Secret<Vec<u8>>>,
+) -> CustomResult<(Vec<u8>, u8, usize), errors::VaultError> {
+    value1_encryption_key
+        .and_then(|value1_encryption_key| {
+            decrypt_split_tokenize_payload(payload, value1_encryption_key, encryption_keys)
+        })
+        .map(|(decrypted_payload, key_index)| {
+            (decrypted_payload, TEMP_LOCKER_KEY_VERSION, key_index)
+        })
+        .or_else(|| {
+            encryption_keys
+                .iter()
+                .enumerate()
+                .find_map(|(key_index, encryption_key)| {
+                    decrypt_tokenize_payload(payload.to_vec(), encryption_key)
+                        .ok()
+                        .map(|(decrypted_payload, token_version)| {
+                            (decrypted_payload, token_version, key_index)
+                        })
+                })
+        })
+        .ok_or(errors::VaultError::DecryptionFailed {
+            key_version: payload.first().copied(),
+        })
+        .attach_printable("Failed to decode redis temp locker data")
+}
+
+/// Runs a redis locker operation, failing it with `VaultError::BackendTimeout` when it does not
+/// complete within the timeout, if any. Without a timeout the operation is bound only by the
+/// timeout of the redis connection.
+async fn with_vault_timeout<T>(
+    timeout: Option<std::time::Duration>,
+    operation: impl std::future::Future<Output = RouterResult<T>>,
+) -> RouterResult<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, operation)
+            .await
+            .change_context(errors::VaultError::BackendTimeout)
+            .map_err(|err| {
+                metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+                VAULT_CIRCUIT_BREAKER.record_failure();
+                err
+            })
             .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed to get redis connection")?;
+            .attach_printable_lazy(|| {
+                format!("Redis locker operation timed out after {timeout:?}")
+            })?,
+        None => operation.await,
+    }
+}
+
+/// Stores the payload against the lookup key if it does not already exist, returning the lookup
+/// key along with whether a new entry was created. The payload is encrypted with the given
+/// algorithm and the primary key of the key provider. When `timeout` is given, the call fails with
+/// `VaultError::BackendTimeout` if redis does not respond within it.
+#[instrument(skip(state, value1, value2, key_provider))]
+pub async fn create_tokenize(
+    state: &routes::SessionState,
+    value1: String,
+    value2: Option<String>,
+    lookup_key: String,
+    key_provider: &impl VaultKeyProvider,
+    algorithm: TempLockerEncryptionAlgorithm,
+    timeout: Option<std::time::Duration>,
+) -> RouterResult<(String, bool)> {
+    let redis_key = get_redis_locker_key(lookup_key.as_str());
+    let func = || async {
+        metrics::CREATED_TOKENIZED_CARD.add(&metrics::CONTEXT, 1, &[]);
 
-        redis_conn
+        let encrypted_payload = encrypt_locker_payload(
+            state,
+            value1.clone(),
+            value2.clone(),
+            lookup_key.clone(),
+            Some(common_utils::date_time::now_unix_timestamp()),
+            key_provider.primary_key(),
+            algorithm,
+        )?;
+
+        let _permit = acquire_vault_operation_permit(state)?;
+        let redis_conn = get_vault_redis_conn(state)?;
+
+        let response = redis_conn
             .set_key_if_not_exists_with_expiry(
                 redis_key.as_str(),
                 bytes::Bytes::from(encrypted_payload),
                 Some(i64::from(consts::LOCKER_REDIS_EXPIRY_SECONDS)),
             )
-            .await
-            .map(|_| lookup_key.clone())
+            .await;
+        VAULT_CIRCUIT_BREAKER.record_outcome(&response);
+
+        response
+            .map(|reply| {
+                (
+                    lookup_key.clone(),
+                    reply == redis_interface::SetnxReply::KeySet,
+                )
+            })
             .map_err(|err| {
                 metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
                 err
@@ -990,13 +2998,20 @@ pub async fn create_tokenize(
             .attach_printable("Error from redis locker")
     };
 
-    match func().await {
-        Ok(s) => {
+    match with_vault_timeout(timeout, func()).await {
+        Ok((lookup_key, true)) => {
             logger::info!(
                 "Insert payload in redis locker successful with lookup key: {:?}",
                 redis_key
             );
-            Ok(s)
+            Ok((lookup_key, true))
+        }
+        Ok((lookup_key, false)) => {
+            logger::warn!(
+                "Payload already exists in redis locker with lookup key: {:?}",
+                redis_key
+            );
+            Ok((lookup_key, false))
         }
         Err(err) => {
             logger::error!("Redis Temp locker Failed: {:?}", err);
@@ -1005,45 +3020,204 @@ pub async fn create_tokenize(
     }
 }
 
-#[instrument(skip(state))]
+/// Replaces the payload stored against the lookup key without modifying its time to live.
+/// `created_at` is the creation time read from the payload being replaced, so that the token
+/// cannot outlive its maximum lifetime by being updated.
+#[instrument(skip(state, value1, value2))]
+pub async fn update_tokenize(
+    state: &routes::SessionState,
+    value1: String,
+    value2: Option<String>,
+    lookup_key: String,
+    created_at: Option<i64>,
+    encryption_key: &masking::Secret<Vec<u8>>,
+    algorithm: TempLockerEncryptionAlgorithm,
+) -> RouterResult<()> {
+    let redis_key = get_redis_locker_key(lookup_key.as_str());
+    let encrypted_payload = encrypt_locker_payload(
+        state,
+        value1,
+        value2,
+        lookup_key,
+        created_at,
+        encryption_key,
+        algorithm,
+    )?;
+
+    let _permit = acquire_vault_operation_permit(state)?;
+    let redis_conn = get_vault_redis_conn(state)?;
+
+    let response = redis_conn
+        .set_key_if_exists_without_modifying_ttl(
+            redis_key.as_str(),
+            bytes::Bytes::from(encrypted_payload),
+        )
+        .await
+        .map_err(|err| {
+            metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+            err
+        })
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error from redis locker")?;
+
+    match response {
+        redis_interface::SetnxReply::KeySet => {
+            logger::info!(
+                "Update payload in redis locker successful with lookup key: {:?}",
+                redis_key
+            );
+            Ok(())
+        }
+        redis_interface::SetnxReply::KeyNotSet => {
+            Err(report!(errors::ApiErrorResponse::UnprocessableEntity {
+                message: "Token is invalid or expired".into(),
+            }))
+        }
+    }
+}
+
+/// Fetches the payload stored against the redis key. Tokens stored before the redis keys of the
+/// tenant were prefixed are read from the unprefixed key instead, and moved under the prefixed key
+/// with their remaining time to live, so that the prefix migration needs no bulk migration job.
+async fn get_locker_payload(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    redis_key: &str,
+) -> CustomResult<bytes::Bytes, redis_interface::errors::RedisError> {
+    match redis_conn
+        .get_key::<Option<bytes::Bytes>>(redis_key)
+        .await?
+    {
+        Some(payload) => Ok(payload),
+        None if redis_conn.key_prefix.is_empty() => {
+            Err(report!(redis_interface::errors::RedisError::NotFound))
+        }
+        None => {
+            let legacy_redis_conn = redis_conn.clone("");
+            let payload = legacy_redis_conn
+                .get_key::<Option<bytes::Bytes>>(redis_key)
+                .await?
+                .ok_or(redis_interface::errors::RedisError::NotFound)?;
+
+            move_legacy_locker_payload(redis_conn, &legacy_redis_conn, redis_key, &payload)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, "Failed to move token under the prefixed redis key")
+                })
+                .ok();
+
+            Ok(payload)
+        }
+    }
+}
+
+async fn move_legacy_locker_payload(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    legacy_redis_conn: &redis_interface::RedisConnectionPool,
+    redis_key: &str,
+    payload: &bytes::Bytes,
+) -> CustomResult<(), redis_interface::errors::RedisError> {
+    let ttl = legacy_redis_conn.get_ttl(redis_key).await?;
+    let ttl = if ttl > 0 {
+        ttl
+    } else {
+        i64::from(consts::LOCKER_REDIS_EXPIRY_SECONDS)
+    };
+
+    redis_conn
+        .set_key_if_not_exists_with_expiry(redis_key, payload.clone(), Some(ttl))
+        .await?;
+    legacy_redis_conn.delete_key(redis_key).await?;
+    metrics::TEMP_LOCKER_LEGACY_KEYS_MOVED.add(&metrics::CONTEXT, 1, &[]);
+
+    Ok(())
+}
+
+/// Fetches the payload stored against the lookup key, trying the primary key of the key provider
+/// before its legacy keys. Payloads read with a legacy key are re-encrypted with the primary key,
+/// failing which the payload is still returned and the re-encryption is retried on the next read.
+/// The payload is re-encrypted with the algorithm configured for `merchant_id` when given. Returns
+/// the version the payload was stored with along with the payload.
+#[instrument(skip(state, key_provider))]
 pub async fn get_tokenized_data(
     state: &routes::SessionState,
     lookup_key: &str,
     _should_get_value2: bool,
-    encryption_key: &masking::Secret<Vec<u8>>,
-) -> RouterResult<api::TokenizePayloadRequest> {
+    key_provider: &impl VaultKeyProvider,
+    expected_service_name: Option<&str>,
+    timeout: Option<std::time::Duration>,
+    merchant_id: Option<&str>,
+) -> RouterResult<(api::TokenizePayloadRequest, u8)> {
+    let (tokenized_data, key_index, token_version) = get_tokenized_data_with_keys(
+        state,
+        lookup_key,
+        &key_provider.encryption_keys(),
+        expected_service_name,
+        timeout,
+    )
+    .await?;
+
+    if key_index != 0 {
+        let reencrypted = async {
+            let algorithm = match merchant_id {
+                Some(merchant_id) => {
+                    get_temp_locker_encryption_algorithm(state, merchant_id).await?
+                }
+                None => state.conf.locker.temp_locker_encryption_algorithm,
+            };
+            update_tokenize(
+                state,
+                tokenized_data.value1.clone(),
+                Some(tokenized_data.value2.clone()),
+                lookup_key.to_owned(),
+                tokenized_data.created_at,
+                key_provider.primary_key(),
+                algorithm,
+            )
+            .await
+        };
+        match reencrypted.await {
+            Ok(()) => metrics::TEMP_LOCKER_REENCRYPTED_TOKENS.add(&metrics::CONTEXT, 1, &[]),
+            Err(error) => logger::error!(
+                ?error,
+                "Failed to re-encrypt token read with a legacy key with the primary key"
+            ),
+        }
+    }
+
+    Ok((tokenized_data, token_version))
+}
+
+/// Fetches the payload stored against the lookup key, decrypting it with the first of
+/// `encryption_keys` that succeeds. Returns the index of the key that was used, and the version
+/// the payload was stored with. When `expected_service_name` is given, payloads stored by any
+/// other service are rejected.
+#[instrument(skip(state, encryption_keys))]
+pub async fn get_tokenized_data_with_keys(
+    state: &routes::SessionState,
+    lookup_key: &str,
+    encryption_keys: &[&masking::Secret<Vec<u8>>],
+    expected_service_name: Option<&str>,
+    timeout: Option<std::time::Duration>,
+) -> RouterResult<(api::TokenizePayloadRequest, usize, u8)> {
     let redis_key = get_redis_locker_key(lookup_key);
+    let value1_encryption_key = get_temp_locker_value1_key(state)?;
     let func = || async {
         metrics::GET_TOKENIZED_CARD.add(&metrics::CONTEXT, 1, &[]);
 
-        let redis_conn = state
-            .store
-            .get_redis_conn()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed to get redis connection")?;
+        let _permit = acquire_vault_operation_permit(state)?;
+        let redis_conn = get_vault_redis_conn(state)?;
 
-        let response = redis_conn.get_key::<bytes::Bytes>(redis_key.as_str()).await;
+        let response = get_locker_payload(&redis_conn, redis_key.as_str()).await;
+        VAULT_CIRCUIT_BREAKER.record_outcome(&response);
 
         match response {
-            Ok(resp) => {
-                let decrypted_payload = GcmAes256
-                    .decode_message(
-                        encryption_key.peek().as_ref(),
-                        masking::Secret::new(resp.into()),
-                    )
-                    .change_context(errors::ApiErrorResponse::InternalServerError)
-                    .attach_printable("Failed to decode redis temp locker data")?;
-
-                let get_response: api::TokenizePayloadRequest =
-                    bytes::Bytes::from(decrypted_payload)
-                        .parse_struct("TokenizePayloadRequest")
-                        .change_context(errors::ApiErrorResponse::InternalServerError)
-                        .attach_printable(
-                            "Error getting TokenizePayloadRequest from tokenize response",
-                        )?;
-
-                Ok(get_response)
-            }
+            Ok(resp) => decode_locker_payload(
+                state,
+                &resp,
+                encryption_keys,
+                value1_encryption_key.as_ref(),
+                expected_service_name,
+            ),
             Err(err) => {
                 metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
                 Err(err).change_context(errors::ApiErrorResponse::UnprocessableEntity {
@@ -1053,7 +3227,7 @@ pub async fn get_tokenized_data(
         }
     };
 
-    match func().await {
+    match with_vault_timeout(timeout, func()).await {
         Ok(s) => {
             logger::info!(
                 "Fetch payload in redis locker successful with lookup key: {:?}",
@@ -1068,22 +3242,53 @@ pub async fn get_tokenized_data(
     }
 }
 
+/// Decrypts a payload read from the redis locker with the first of `encryption_keys` that
+/// succeeds, and verifies that it was stored for the tenant and the expected service. Returns the
+/// index of the key that was used, and the version the payload was stored with.
+fn decode_locker_payload(
+    state: &routes::SessionState,
+    payload: &[u8],
+    encryption_keys: &[&masking::Secret<Vec<u8>>],
+    value1_encryption_key: Option<&masking::Secret<Vec<u8>>>,
+    expected_service_name: Option<&str>,
+) -> RouterResult<(api::TokenizePayloadRequest, usize, u8)> {
+    let (decrypted_payload, token_version, key_index) =
+        decrypt_tokenize_payload_with_keys(payload, encryption_keys, value1_encryption_key)
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let get_response: api::TokenizePayloadRequest = bytes::Bytes::from(decrypted_payload)
+        .parse_struct("TokenizePayloadRequest")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error getting TokenizePayloadRequest from tokenize response")?;
+    verify_token_tenant(&get_response, &state.tenant.name).change_context(
+        errors::ApiErrorResponse::UnprocessableEntity {
+            message: "Token is invalid or expired".into(),
+        },
+    )?;
+    verify_token_service_name(&get_response, expected_service_name).change_context(
+        errors::ApiErrorResponse::UnprocessableEntity {
+            message: "Token is invalid or expired".into(),
+        },
+    )?;
+
+    Ok((get_response, key_index, token_version))
+}
+
 #[instrument(skip(state))]
 pub async fn delete_tokenized_data(
     state: &routes::SessionState,
     lookup_key: &str,
+    timeout: Option<std::time::Duration>,
 ) -> RouterResult<()> {
     let redis_key = get_redis_locker_key(lookup_key);
     let func = || async {
         metrics::DELETED_TOKENIZED_CARD.add(&metrics::CONTEXT, 1, &[]);
 
-        let redis_conn = state
-            .store
-            .get_redis_conn()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed to get redis connection")?;
+        let _permit = acquire_vault_operation_permit(state)?;
+        let redis_conn = get_vault_redis_conn(state)?;
 
         let response = redis_conn.delete_key(redis_key.as_str()).await;
+        VAULT_CIRCUIT_BREAKER.record_outcome(&response);
 
         match response {
             Ok(redis_interface::DelReply::KeyDeleted) => Ok(()),
@@ -1099,7 +3304,7 @@ pub async fn delete_tokenized_data(
             }
         }
     };
-    match func().await {
+    match with_vault_timeout(timeout, func()).await {
         Ok(s) => {
             logger::info!(
                 "Delete payload in redis locker successful with lookup key: {:?}",
@@ -1114,17 +3319,55 @@ pub async fn delete_tokenized_data(
     }
 }
 
+/// Deletes the payloads stored against many lookup keys at once, pipelining the deletions over the
+/// redis connection. Returns whether each payload is gone, which is also the case for payloads that
+/// were already deleted or expired.
+#[instrument(skip(state))]
+pub async fn delete_tokenized_data_batch(
+    state: &routes::SessionState,
+    lookup_keys: Vec<String>,
+) -> RouterResult<Vec<(String, bool)>> {
+    let _permit = acquire_vault_operation_permit(state)?;
+    let redis_conn = get_vault_redis_conn(state)?;
+    let redis_keys = lookup_keys
+        .iter()
+        .map(|lookup_key| get_redis_locker_key(lookup_key))
+        .collect::<Vec<_>>();
+
+    let responses = redis_conn.delete_multiple_keys(&redis_keys).await;
+
+    Ok(lookup_keys
+        .into_iter()
+        .zip(responses)
+        .map(|(lookup_key, response)| match response {
+            Ok(redis_interface::DelReply::KeyDeleted) => {
+                metrics::DELETED_TOKENIZED_CARD.add(&metrics::CONTEXT, 1, &[]);
+                (lookup_key, true)
+            }
+            Ok(redis_interface::DelReply::KeyNotDeleted) => (lookup_key, true),
+            Err(error) => {
+                metrics::TEMP_LOCKER_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+                logger::error!(
+                    ?error,
+                    "Failed to delete from redis locker with lookup key: {lookup_key}"
+                );
+                (lookup_key, false)
+            }
+        })
+        .collect())
+}
+
 // ********************************************** PROCESS TRACKER **********************************************
 
 pub async fn add_delete_tokenized_data_task(
     db: &dyn db::StorageInterface,
     lookup_key: &str,
     pm: enums::PaymentMethod,
+    tag: &str,
 ) -> RouterResult<()> {
     let runner = storage::ProcessTrackerRunner::DeleteTokenizeDataWorkflow;
     let process_tracker_id = format!("{runner}_{lookup_key}");
     let task = runner.to_string();
-    let tag = ["BASILISK-V3"];
     let tracking_data = storage::TokenizeCoreWorkflow {
         lookup_key: lookup_key.to_owned(),
         pm,
@@ -1138,7 +3381,7 @@ pub async fn add_delete_tokenized_data_task(
         process_tracker_id,
         &task,
         runner,
-        tag,
+        [tag],
         tracking_data,
         schedule_time,
     )
@@ -1171,7 +3414,7 @@ pub async fn start_tokenize_data_workflow(
         )
     })?;
 
-    match delete_tokenized_data(state, &delete_tokenize_data.lookup_key).await {
+    match delete_tokenized_data(state, &delete_tokenize_data.lookup_key, None).await {
         Ok(()) => {
             logger::info!("Card From locker deleted Successfully");
             //mark task as finished
@@ -1192,22 +3435,46 @@ pub async fn start_tokenize_data_workflow(
     Ok(())
 }
 
+/// Time to live of the in-memory delete tokenize retry mappings, 5 mins
+const PT_MAPPING_CACHE_TTL: u64 = 5 * 60;
+
+/// Delete tokenize retry mappings rarely change, so they are kept in memory to avoid a redis read
+/// for every schedule computation when many delete tasks retry at once
+static PT_MAPPING_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new(PT_MAPPING_CACHE_TTL, PT_MAPPING_CACHE_TTL, None));
+
 pub async fn get_delete_tokenize_schedule_time(
     db: &dyn db::StorageInterface,
     pm: &enums::PaymentMethod,
     retry_count: i32,
 ) -> Option<time::PrimitiveDateTime> {
-    let redis_mapping = db::get_and_deserialize_key(
-        db,
-        &format!("pt_mapping_delete_{pm}_tokenize_data"),
-        "PaymentMethodsPTMapping",
-    )
-    .await;
-    let mapping = match redis_mapping {
-        Ok(x) => x,
-        Err(error) => {
-            logger::info!(?error, "Redis Mapping Error");
-            process_data::PaymentMethodsPTMapping::default()
+    let mapping_key = format!("pt_mapping_delete_{pm}_tokenize_data");
+    let cache_key = CacheKey {
+        key: mapping_key.clone(),
+        prefix: db
+            .get_redis_conn()
+            .map(|redis_conn| redis_conn.key_prefix.clone())
+            .unwrap_or_default(),
+    };
+
+    let mapping = match PT_MAPPING_CACHE
+        .get_val::<process_data::PaymentMethodsPTMapping>(cache_key.clone())
+        .await
+    {
+        Some(mapping) => mapping,
+        None => {
+            let redis_mapping: CustomResult<process_data::PaymentMethodsPTMapping, _> =
+                db::get_and_deserialize_key(db, &mapping_key, "PaymentMethodsPTMapping").await;
+            match redis_mapping {
+                Ok(mapping) => {
+                    PT_MAPPING_CACHE.push(cache_key, mapping.clone()).await;
+                    mapping
+                }
+                Err(error) => {
+                    logger::info!(?error, "Redis Mapping Error");
+                    process_data::PaymentMethodsPTMapping::default()
+                }
+            }
         }
     };
     let time_delta = process_tracker_utils::get_pm_schedule_time(mapping, pm, retry_count + 1);
@@ -1250,4 +3517,916 @@ pub async fn retry_delete_tokenize(
     }
 }
 
+pub async fn list_failed_delete_tokenize_tasks(
+    db: &dyn db::StorageInterface,
+    limit: Option<i64>,
+) -> RouterResult<Vec<storage::ProcessTracker>> {
+    db.find_processes_by_runner_business_status(
+        storage::ProcessTrackerRunner::DeleteTokenizeDataWorkflow,
+        diesel_models::process_tracker::business_status::RETRIES_EXCEEDED,
+        limit,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to fetch delete tokenize tasks that exceeded retries")
+}
+
+pub async fn requeue_failed_delete_tokenize(
+    db: &dyn db::StorageInterface,
+    process_id: &str,
+) -> RouterResult<()> {
+    let process = db
+        .find_process_by_id(process_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch delete tokenize process tracker task")?
+        .ok_or(errors::ApiErrorResponse::GenericNotFoundError {
+            message: format!("Process tracker task {process_id} not found"),
+        })?;
+
+    let is_failed_delete_tokenize = process.runner.as_deref()
+        == Some(
+            storage::ProcessTrackerRunner::DeleteTokenizeDataWorkflow
+                .to_string()
+                .as_str(),
+        )
+        && process.is_valid_business_status(&[
+            diesel_models::process_tracker::business_status::RETRIES_EXCEEDED,
+        ]);
+    if !is_failed_delete_tokenize {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Process tracker task {process_id} is not a failed delete tokenize task"
+            ),
+        }));
+    }
+
+    let delete_tokenize_data =
+        serde_json::from_value::<storage::TokenizeCoreWorkflow>(process.tracking_data.clone())
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse delete tokenize tracking data")?;
+    let schedule_time = get_delete_tokenize_schedule_time(db, &delete_tokenize_data.pm, 0)
+        .await
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to obtain process tracker schedule time")?;
+
+    db.update_process(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: Some(0),
+            schedule_time: Some(schedule_time),
+            tracking_data: None,
+            business_status: Some(String::from(
+                diesel_models::process_tracker::business_status::PENDING,
+            )),
+            status: Some(enums::ProcessTrackerStatus::New),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to requeue delete tokenize process tracker task")?;
+
+    Ok(())
+}
+
+/// Completes pending delete tokenize tasks whose token is already gone from the temporary locker,
+/// such as after an out of band flush, so that the scheduler does not keep retrying them.
+/// Returns the number of tasks reaped.
+pub async fn reap_orphaned_delete_tokenize_tasks(
+    state: &routes::SessionState,
+    limit: Option<i64>,
+) -> RouterResult<usize> {
+    let db = &*state.store;
+    let pending_tasks = db
+        .find_processes_by_runner_business_status(
+            storage::ProcessTrackerRunner::DeleteTokenizeDataWorkflow,
+            diesel_models::process_tracker::business_status::PENDING,
+            limit,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch pending delete tokenize tasks")?;
+
+    let mut reaped_count = 0;
+    for task in pending_tasks {
+        let delete_tokenize_data = match serde_json::from_value::<storage::TokenizeCoreWorkflow>(
+            task.tracking_data.clone(),
+        ) {
+            Ok(delete_tokenize_data) => delete_tokenize_data,
+            Err(error) => {
+                logger::warn!(
+                    ?error,
+                    process_id = %task.id,
+                    "Failed to parse delete tokenize tracking data"
+                );
+                continue;
+            }
+        };
+
+        if Vault::token_exists(state, &delete_tokenize_data.lookup_key).await? {
+            continue;
+        }
+
+        db.as_scheduler()
+            .finish_process_with_business_status(
+                task,
+                diesel_models::process_tracker::business_status::COMPLETED_BY_PT,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to complete orphaned delete tokenize task")?;
+        metrics::REAPED_DELETE_TOKENIZE_TASKS_COUNT.add(&metrics::CONTEXT, 1, &[]);
+        reaped_count += 1;
+    }
+
+    logger::info!("Reaped {reaped_count} orphaned delete tokenize tasks");
+    Ok(reaped_count)
+}
+
 // Fallback logic of old temp locker needs to be removed later
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn test_card(cvc: &str) -> api::Card {
+        api::Card {
+            card_number: "4111111111111111".parse().unwrap(),
+            card_exp_month: "12".to_string().into(),
+            card_exp_year: "2030".to_string().into(),
+            card_holder_name: None,
+            card_cvc: cvc.to_string().into(),
+            card_issuer: None,
+            card_network: None,
+            card_type: None,
+            card_issuing_country: None,
+            bank_code: None,
+            nick_name: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_with_vault_timeout() {
+        let timed_out = with_vault_timeout(
+            Some(std::time::Duration::from_millis(10)),
+            futures::future::pending::<RouterResult<()>>(),
+        )
+        .await;
+        assert!(timed_out.is_err_and(|error| matches!(
+            error.downcast_ref::<errors::VaultError>(),
+            Some(errors::VaultError::BackendTimeout)
+        )));
+
+        let completed =
+            with_vault_timeout(Some(std::time::Duration::from_secs(1)), async { Ok(1) }).await;
+        assert_eq!(completed.ok(), Some(1));
+        assert_eq!(
+            with_vault_timeout(None, async { Ok(1) }).await.ok(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_vault_key_kind_lookup_keys_are_distinguishable() {
+        let payment_key = VaultKeyKind::PaymentToken.generate_lookup_key();
+        let payout_key = VaultKeyKind::PayoutToken.generate_lookup_key();
+
+        assert!(payment_key.starts_with("token_"));
+        assert!(payout_key.starts_with("temporary_token_"));
+        assert_eq!(payment_key.len(), "token_".len() + ID_LENGTH);
+        assert_eq!(
+            payout_key.len(),
+            "temporary_token_".len() + PAYOUT_TOKEN_ID_LENGTH
+        );
+    }
+
+    #[cfg(feature = "payouts")]
+    #[test]
+    fn test_bank_payout_verification_status() {
+        let bank = api::BankPayout::Ach(payouts::AchBankTransfer {
+            bank_account_number: "000123456".to_string().into(),
+            bank_routing_number: "110000000".to_string().into(),
+            ..Default::default()
+        });
+        let payout_method = api::PayoutMethodData::Bank(bank.clone());
+
+        // Newly vaulted accounts are unverified
+        let value1 = payout_method.get_value1(None).unwrap();
+        let value2 = payout_method.get_value2(None).unwrap();
+        assert_eq!(
+            get_bank_verification_status(value2.clone()).unwrap(),
+            BankVerificationStatus::Unverified
+        );
+
+        let verified_value2 =
+            set_bank_verification_status(value2, BankVerificationStatus::Verified).unwrap();
+        assert_eq!(
+            get_bank_verification_status(verified_value2.clone()).unwrap(),
+            BankVerificationStatus::Verified
+        );
+        let (restored_payout_method, _) =
+            api::PayoutMethodData::from_values(value1, verified_value2).unwrap();
+        assert!(matches!(
+            restored_payout_method,
+            api::PayoutMethodData::Bank(restored_bank) if restored_bank == bank
+        ));
+
+        let card_value2 = r#"{"type":"card","value":"{}"}"#.to_string();
+        assert!(
+            get_bank_verification_status(card_value2).is_err_and(|error| matches!(
+                error.current_context(),
+                errors::VaultError::TokenMethodMismatch
+            ))
+        );
+    }
+
+    #[cfg(feature = "payouts")]
+    #[test]
+    fn test_supported_payout_types() {
+        use strum::IntoEnumIterator;
+
+        assert_eq!(
+            VaultPayoutMethod::get_supported_payout_types(common_enums::PayoutType::iter()),
+            vec![
+                common_enums::PayoutType::Card,
+                common_enums::PayoutType::Bank,
+                common_enums::PayoutType::Wallet,
+            ]
+        );
+        assert_eq!(
+            VaultPayoutMethod::get_supported_payout_types([common_enums::PayoutType::Wallet]),
+            vec![common_enums::PayoutType::Wallet]
+        );
+    }
+
+    #[test]
+    fn test_decrypt_versioned_and_legacy_tokenize_payloads() {
+        let encryption_key = masking::Secret::new(vec![7; 32]);
+
+        let versioned_payload = encrypt_tokenize_payload(
+            "value1".to_string(),
+            None,
+            "token_123".to_string(),
+            "public".to_string(),
+            None,
+            TempLockerEncryptionAlgorithm::GcmAes256,
+            &encryption_key,
+        )
+        .unwrap();
+        assert_eq!(versioned_payload.first(), Some(&TEMP_LOCKER_KEY_VERSION));
+        let (decrypted_payload, token_version) =
+            decrypt_tokenize_payload(versioned_payload, &encryption_key).unwrap();
+        assert_eq!(token_version, TEMP_LOCKER_KEY_VERSION);
+        assert!(String::from_utf8(decrypted_payload)
+            .unwrap()
+            .contains("token_123"));
+
+        let legacy_payload = GcmAes256
+            .encode_message(encryption_key.peek().as_ref(), b"legacy payload")
+            .unwrap();
+        let (decrypted_payload, token_version) =
+            decrypt_tokenize_payload(legacy_payload, &encryption_key).unwrap();
+        assert_eq!(decrypted_payload, b"legacy payload");
+        assert_eq!(token_version, LEGACY_TOKEN_VERSION);
+
+        let headerless_payload = [TEMP_LOCKER_KEY_VERSION]
+            .into_iter()
+            .chain(
+                GcmAes256
+                    .encode_message(encryption_key.peek().as_ref(), b"headerless payload")
+                    .unwrap(),
+            )
+            .collect();
+        let (decrypted_payload, _) =
+            decrypt_tokenize_payload(headerless_payload, &encryption_key).unwrap();
+        assert_eq!(decrypted_payload, b"headerless payload");
+    }
+
+    #[test]
+    fn test_decrypt_tokenize_payload_with_rotating_keys() {
+        let primary_key = masking::Secret::new(vec![7; 32]);
+        let legacy_key = masking::Secret::new(vec![8; 32]);
+        let key_provider = RotatingVaultKeys::new(primary_key.clone(), vec![legacy_key.clone()]);
+        let encryption_keys = key_provider.encryption_keys();
+        let encrypt = |encryption_key: &masking::Secret<Vec<u8>>| {
+            encrypt_tokenize_payload(
+                "value1".to_string(),
+                None,
+                "token_123".to_string(),
+                "public".to_string(),
+                None,
+                TempLockerEncryptionAlgorithm::GcmAes256,
+                encryption_key,
+            )
+            .unwrap()
+        };
+
+        let (_, _, key_index) =
+            decrypt_tokenize_payload_with_keys(&encrypt(&primary_key), &encryption_keys, None)
+                .unwrap();
+        assert_eq!(key_index, 0);
+
+        let (decrypted_payload, token_version, key_index) =
+            decrypt_tokenize_payload_with_keys(&encrypt(&legacy_key), &encryption_keys, None)
+                .unwrap();
+        assert_eq!(key_index, 1);
+        assert_eq!(token_version, TEMP_LOCKER_KEY_VERSION);
+        assert!(String::from_utf8(decrypted_payload)
+            .unwrap()
+            .contains("token_123"));
+
+        let unknown_key = masking::Secret::new(vec![9; 32]);
+        assert!(
+            decrypt_tokenize_payload_with_keys(&encrypt(&unknown_key), &encryption_keys, None)
+                .is_err_and(|error| matches!(
+                    error.current_context(),
+                    errors::VaultError::DecryptionFailed {
+                        key_version: Some(TEMP_LOCKER_KEY_VERSION)
+                    }
+                ))
+        );
+        // Payloads encrypted with a legacy key are unreadable once the rotation is over
+        assert!(decrypt_tokenize_payload_with_keys(
+            &encrypt(&legacy_key),
+            &primary_key.encryption_keys(),
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_split_tokenize_payload_round_trip() {
+        let value1_encryption_key = masking::Secret::new(vec![5; 32]);
+        let encryption_key = masking::Secret::new(vec![7; 32]);
+
+        let encrypted_payload = encrypt_split_tokenize_payload(
+            "value1".to_string(),
+            Some("value2".to_string()),
+            "token_123".to_string(),
+            "public".to_string(),
+            None,
+            TempLockerEncryptionAlgorithm::ChaCha20Poly1305,
+            &value1_encryption_key,
+            &encryption_key,
+        )
+        .unwrap();
+        assert_eq!(
+            encrypted_payload.get(..2),
+            Some([TEMP_LOCKER_KEY_VERSION, TEMP_LOCKER_SPLIT_VALUES_HEADER].as_slice())
+        );
+
+        let (decrypted_payload, token_version, _) = decrypt_tokenize_payload_with_keys(
+            &encrypted_payload,
+            &[&encryption_key],
+            Some(&value1_encryption_key),
+        )
+        .unwrap();
+        let tokenize_payload: api::TokenizePayloadRequest =
+            serde_json::from_slice(&decrypted_payload).unwrap();
+        assert_eq!(token_version, TEMP_LOCKER_KEY_VERSION);
+        assert_eq!(tokenize_payload.value1, "value1");
+        assert_eq!(tokenize_payload.value2, "value2");
+        assert_eq!(tokenize_payload.lookup_key, "token_123");
+
+        // value1 is unreadable without its own key, even with the key of the rest of the payload
+        assert!(
+            decrypt_tokenize_payload_with_keys(&encrypted_payload, &[&encryption_key], None)
+                .is_err()
+        );
+        assert!(decrypt_tokenize_payload_with_keys(
+            &encrypted_payload,
+            &[&encryption_key],
+            Some(&encryption_key)
+        )
+        .is_err());
+
+        // Payloads encrypted as a whole remain readable with a value1 key configured
+        let whole_payload = encrypt_tokenize_payload(
+            "value1".to_string(),
+            None,
+            "token_123".to_string(),
+            "public".to_string(),
+            None,
+            TempLockerEncryptionAlgorithm::GcmAes256,
+            &encryption_key,
+        )
+        .unwrap();
+        assert!(decrypt_tokenize_payload_with_keys(
+            &whole_payload,
+            &[&encryption_key],
+            Some(&value1_encryption_key)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_tokenize_payload_round_trip_for_each_algorithm() {
+        let encryption_key = masking::Secret::new(vec![7; 32]);
+
+        for (algorithm, algorithm_header) in [
+            (
+                TempLockerEncryptionAlgorithm::GcmAes256,
+                TEMP_LOCKER_GCM_AES_256_HEADER,
+            ),
+            (
+                TempLockerEncryptionAlgorithm::ChaCha20Poly1305,
+                TEMP_LOCKER_CHACHA20_POLY1305_HEADER,
+            ),
+        ] {
+            let encrypted_payload = encrypt_tokenize_payload(
+                "value1".to_string(),
+                Some("value2".to_string()),
+                "token_123".to_string(),
+                "public".to_string(),
+                None,
+                algorithm,
+                &encryption_key,
+            )
+            .unwrap();
+            assert_eq!(encrypted_payload.get(1), Some(&algorithm_header));
+
+            let (decrypted_payload, _) =
+                decrypt_tokenize_payload(encrypted_payload, &encryption_key).unwrap();
+            let tokenize_payload: api::TokenizePayloadRequest =
+                serde_json::from_slice(&decrypted_payload).unwrap();
+            assert_eq!(tokenize_payload.value1, "value1");
+            assert_eq!(tokenize_payload.value2, "value2");
+            assert_eq!(tokenize_payload.lookup_key, "token_123");
+        }
+    }
+
+    #[test]
+    fn test_verify_token_tenant() {
+        let mut payload = api::TokenizePayloadRequest {
+            value1: "value1".to_string(),
+            value2: String::new(),
+            lookup_key: "token_123".to_string(),
+            service_name: VAULT_SERVICE_NAME.to_string(),
+            tenant_id: Some("tenant_a".to_string()),
+            created_at: None,
+        };
+        assert!(verify_token_tenant(&payload, "tenant_a").is_ok());
+        assert!(matches!(
+            verify_token_tenant(&payload, "tenant_b")
+                .unwrap_err()
+                .current_context(),
+            errors::VaultError::TenantMismatch
+        ));
+
+        payload.tenant_id = None;
+        assert!(verify_token_tenant(&payload, common_utils::consts::DEFAULT_TENANT).is_ok());
+        assert!(verify_token_tenant(&payload, "tenant_a").is_err());
+    }
+
+    #[test]
+    fn test_verify_token_service_name() {
+        let payload = api::TokenizePayloadRequest {
+            value1: "value1".to_string(),
+            value2: String::new(),
+            lookup_key: "token_123".to_string(),
+            service_name: VAULT_SERVICE_NAME.to_string(),
+            tenant_id: None,
+            created_at: None,
+        };
+        assert!(verify_token_service_name(&payload, None).is_ok());
+        assert!(verify_token_service_name(&payload, Some(VAULT_SERVICE_NAME)).is_ok());
+        assert!(matches!(
+            verify_token_service_name(&payload, Some("BANK"))
+                .unwrap_err()
+                .current_context(),
+            errors::VaultError::ServiceNameMismatch
+        ));
+    }
+
+    #[test]
+    fn test_vault_circuit_breaker_opens_after_consecutive_failures() {
+        let circuit_breaker = VaultCircuitBreaker {
+            consecutive_failures: std::sync::atomic::AtomicU32::new(0),
+            opened_at: std::sync::Mutex::new(None),
+        };
+
+        for _ in 1..VAULT_CIRCUIT_FAILURE_THRESHOLD {
+            circuit_breaker.record_failure();
+        }
+        assert!(circuit_breaker.check().is_ok());
+
+        circuit_breaker.record_failure();
+        assert!(circuit_breaker.check().is_err());
+
+        circuit_breaker.record_success();
+        assert!(circuit_breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_vault_circuit_breaker_records_command_outcomes() {
+        let circuit_breaker = VaultCircuitBreaker {
+            consecutive_failures: std::sync::atomic::AtomicU32::new(0),
+            opened_at: std::sync::Mutex::new(None),
+        };
+        let failed: CustomResult<(), redis_interface::errors::RedisError> =
+            Err(report!(redis_interface::errors::RedisError::GetFailed));
+        let not_found: CustomResult<(), redis_interface::errors::RedisError> =
+            Err(report!(redis_interface::errors::RedisError::NotFound));
+
+        for _ in 0..VAULT_CIRCUIT_FAILURE_THRESHOLD {
+            circuit_breaker.record_outcome(&failed);
+        }
+        assert!(circuit_breaker.check().is_err());
+
+        // A probe whose command fails keeps the circuit open
+        *circuit_breaker.opened_at.lock().unwrap() =
+            std::time::Instant::now().checked_sub(VAULT_CIRCUIT_COOLDOWN);
+        assert!(circuit_breaker.check().is_ok());
+        circuit_breaker.record_outcome(&failed);
+        assert!(circuit_breaker.check().is_err());
+
+        // A probe whose command gets a response closes the circuit
+        *circuit_breaker.opened_at.lock().unwrap() =
+            std::time::Instant::now().checked_sub(VAULT_CIRCUIT_COOLDOWN);
+        assert!(circuit_breaker.check().is_ok());
+        circuit_breaker.record_outcome(&not_found);
+        assert!(circuit_breaker.check().is_ok());
+    }
+
+    #[cfg(feature = "payouts")]
+    #[test]
+    fn test_payout_method_from_values_method_mismatch() {
+        let value1 = r#"{"type":"card","value":"{}"}"#.to_string();
+        let value2 = r#"{"type":"bank","value":"{}"}"#.to_string();
+
+        let result = api::PayoutMethodData::from_values(value1, value2);
+        assert!(result.is_err_and(|error| matches!(
+            error.current_context(),
+            errors::VaultError::TokenMethodMismatch
+        )));
+    }
+
+    #[cfg(feature = "payouts")]
+    #[test]
+    fn test_payout_method_from_values_unsupported_method() {
+        let value1 = r#"{"type":"upi","value":"{}"}"#.to_string();
+        let value2 = r#"{"type":"upi","value":"{}"}"#.to_string();
+
+        let result = api::PayoutMethodData::from_values(value1, value2);
+        assert!(result.is_err_and(|error| matches!(
+            error.current_context(),
+            errors::VaultError::PayoutMethodNotSupported
+        )));
+    }
+
+    #[test]
+    fn test_card_value2_without_cvc() {
+        let card = test_card("737");
+        let payment_method = api::PaymentMethodData::Card(card);
+
+        let value2 = get_payment_method_value2(&payment_method, None, false, None, None).unwrap();
+        assert!(!value2.contains("737"));
+
+        let value2 = get_payment_method_value2(&payment_method, None, true, None, None).unwrap();
+        assert!(value2.contains("737"));
+    }
+
+    #[test]
+    fn test_replaced_card_value2_without_cvc() {
+        let payment_method = api::PaymentMethodData::Card(test_card("737"));
+        let billing_address = api_models::payments::Address {
+            address: Some(api_models::payments::AddressDetails {
+                zip: Some("94122".to_string().into()),
+                ..Default::default()
+            }),
+            phone: None,
+            email: None,
+        };
+        let supp_data = SupplementaryVaultData {
+            customer_id: None,
+            payment_method_id: Some("pm_saved_card".to_string()),
+            billing_address: Some(billing_address.clone()),
+            token_version: LEGACY_TOKEN_VERSION,
+        };
+
+        let value2 = get_replaced_payment_method_value2(&payment_method, supp_data, false).unwrap();
+        let card_value2 = match serde_json::from_str(&value2).unwrap() {
+            VaultPaymentMethod::Card(card_value2) => Some(card_value2),
+            _ => None,
+        };
+        let card_value2: api::TokenizedCardValue2 =
+            serde_json::from_str(&card_value2.unwrap()).unwrap();
+        assert_eq!(card_value2.card_security_code, None);
+        // The supplementary data of the replaced token is kept
+        assert_eq!(card_value2.billing_address, Some(billing_address));
+        assert_eq!(
+            card_value2.payment_method_id,
+            Some("pm_saved_card".to_string())
+        );
+    }
+
+    #[test]
+    fn test_card_billing_address_round_trip() {
+        let card = test_card("737");
+        let payment_method = api::PaymentMethodData::Card(card);
+        let billing_address = api_models::payments::Address {
+            address: Some(api_models::payments::AddressDetails {
+                line1: Some("1467 Harrison Street".to_string().into()),
+                zip: Some("94122".to_string().into()),
+                country: Some(common_enums::CountryAlpha2::US),
+                ..Default::default()
+            }),
+            phone: None,
+            email: None,
+        };
+
+        let value1 = payment_method.get_value1(None).unwrap();
+        let value2 =
+            get_payment_method_value2(&payment_method, None, true, Some(&billing_address), None)
+                .unwrap();
+        let (_, supp_data) = api::PaymentMethodData::from_values(value1.clone(), value2).unwrap();
+        assert_eq!(supp_data.billing_address, Some(billing_address));
+
+        // Tokens stored without an address must still decode
+        let value2 = get_payment_method_value2(&payment_method, None, true, None, None).unwrap();
+        let (_, supp_data) = api::PaymentMethodData::from_values(value1, value2).unwrap();
+        assert_eq!(supp_data.billing_address, None);
+    }
+
+    #[test]
+    fn test_card_payment_method_id_round_trip() {
+        let card = test_card("123");
+        let payment_method = api::PaymentMethodData::Card(card);
+        let payment_method_id = "pm_saved_card".to_string();
+
+        let value1 = payment_method.get_value1(None).unwrap();
+        let value2 = get_payment_method_value2(
+            &payment_method,
+            None,
+            true,
+            None,
+            Some(payment_method_id.clone()),
+        )
+        .unwrap();
+        let (_, supp_data) = api::PaymentMethodData::from_values(value1, value2).unwrap();
+        assert_eq!(supp_data.payment_method_id, Some(payment_method_id));
+    }
+
+    #[test]
+    fn test_vaultable_payment_methods() {
+        use strum::IntoEnumIterator;
+
+        let supported_payment_methods = enums::PaymentMethod::iter()
+            .filter(|payment_method| VaultPaymentMethod::is_supported(*payment_method))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            supported_payment_methods,
+            vec![
+                enums::PaymentMethod::Card,
+                enums::PaymentMethod::Wallet,
+                enums::PaymentMethod::BankRedirect,
+                enums::PaymentMethod::BankTransfer,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bank_redirect_dedup_hash() {
+        let hash_key = b"dedup-hash-key";
+        let giropay = |iban: Option<&str>| api_models::payments::BankRedirectData::Giropay {
+            billing_details: None,
+            bank_account_bic: None,
+            bank_account_iban: iban.map(|iban| masking::Secret::new(iban.to_string())),
+            country: None,
+        };
+
+        let first = giropay(Some("DE89370400440532013000")).dedup_hash(hash_key);
+        assert!(first.is_some());
+        assert_eq!(
+            first,
+            giropay(Some("DE89370400440532013000")).dedup_hash(hash_key)
+        );
+        assert_ne!(
+            first,
+            giropay(Some("DE02120300000000202051")).dedup_hash(hash_key)
+        );
+        assert_eq!(giropay(None).dedup_hash(hash_key), None);
+    }
+
+    #[test]
+    fn test_card_display_from_value1() {
+        let card = test_card("737");
+        let value1 = api::PaymentMethodData::Card(card).get_value1(None).unwrap();
+
+        let (card_display, card_isin) = get_card_display_from_value1(value1).unwrap();
+
+        assert_eq!(
+            card_display,
+            CardDisplay {
+                last4: "1111".to_string(),
+                exp_month: "12".to_string().into(),
+                exp_year: "2030".to_string().into(),
+                network: None,
+            }
+        );
+        assert_eq!(card_isin, "411111");
+    }
+
+    #[test]
+    fn test_empty_value2_is_corrupt_token() {
+        let value1 = serde_json::json!({ "type": "card", "value": "{}" }).to_string();
+
+        for value2 in ["", "  \n"] {
+            let result = api::PaymentMethodData::from_values(value1.clone(), value2.to_string());
+            assert!(result.is_err_and(|error| matches!(
+                error.current_context(),
+                errors::VaultError::CorruptToken { .. }
+            )));
+        }
+    }
+
+    #[test]
+    fn test_card_from_values_reports_failing_field() {
+        let card_value1 = |card_number: &str| {
+            serde_json::json!({
+                "card_number": card_number,
+                "exp_year": "2030",
+                "exp_month": "12",
+            })
+            .to_string()
+        };
+        let card_value2 = |card_security_code: &str| {
+            serde_json::json!({ "card_security_code": card_security_code }).to_string()
+        };
+        let failing_field = |value1: String, value2: String| {
+            api::Card::from_values(value1, value2)
+                .err()
+                .and_then(|error| match error.current_context() {
+                    errors::VaultError::FieldDecodeFailed { field } => Some(*field),
+                    _ => None,
+                })
+        };
+
+        assert!(
+            api::Card::from_values(card_value1("4111111111111111"), card_value2("123")).is_ok()
+        );
+        assert_eq!(
+            failing_field(card_value1("4111"), card_value2("123")),
+            Some("card_number")
+        );
+        assert_eq!(
+            failing_field(card_value1("4111111111111111"), card_value2("12a")),
+            Some("card_security_code")
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct GiftVoucher(String);
+
+    impl Vaultable for GiftVoucher {
+        fn get_value1(
+            &self,
+            _customer_id: Option<id_type::CustomerId>,
+        ) -> CustomResult<String, errors::VaultError> {
+            Ok(self.0.clone())
+        }
+
+        fn from_values(
+            value1: String,
+            _value2: String,
+        ) -> CustomResult<(Self, SupplementaryVaultData), errors::VaultError> {
+            let supp_data = SupplementaryVaultData {
+                customer_id: None,
+                payment_method_id: None,
+                billing_address: None,
+                token_version: LEGACY_TOKEN_VERSION,
+            };
+            Ok((Self(value1), supp_data))
+        }
+    }
+
+    impl CustomVaultable for GiftVoucher {
+        const TYPE_TAG: &'static str = "gift_voucher";
+    }
+
+    #[test]
+    fn test_custom_vaultable_round_trip() {
+        register_custom_vaultable::<GiftVoucher>();
+        let voucher = GiftVoucher("voucher_code".to_string());
+
+        let (value1, value2) = get_custom_vault_values(&voucher, None).unwrap();
+        let (type_tag, data, _) =
+            decode_custom_vault_values(value1.clone(), value2.clone()).unwrap();
+        assert_eq!(type_tag, GiftVoucher::TYPE_TAG);
+        assert_eq!(data.downcast_ref::<GiftVoucher>(), Some(&voucher));
+
+        assert!(
+            api::PaymentMethodData::from_values(value1, value2).is_err_and(|error| matches!(
+                error.current_context(),
+                errors::VaultError::PaymentMethodNotSupported
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unregistered_custom_vaultable_is_rejected() {
+        let value =
+            serde_json::json!({ "type": "custom", "value": ["unregistered", "value"] }).to_string();
+
+        assert!(
+            decode_custom_vault_values(value.clone(), value).is_err_and(|error| matches!(
+                error.current_context(),
+                errors::VaultError::PaymentMethodNotSupported
+            ))
+        );
+    }
+
+    #[test]
+    fn test_vault_payment_method_kind_from_value1() {
+        let value1 =
+            serde_json::to_string(&VaultPaymentMethod::BankTransfer("not json".to_string()))
+                .unwrap();
+        assert_eq!(
+            VaultPaymentMethodKind::from_value1(&value1),
+            Some(VaultPaymentMethodKind::BankTransfer)
+        );
+
+        let value1 = serde_json::to_string(&VaultPaymentMethod::Custom(
+            "gift_card".to_string(),
+            "{}".to_string(),
+        ))
+        .unwrap();
+        assert_eq!(
+            VaultPaymentMethodKind::from_value1(&value1),
+            Some(VaultPaymentMethodKind::Custom)
+        );
+
+        assert_eq!(
+            VaultPaymentMethodKind::from_value1(r#"{"card_number":"4111111111111111"}"#),
+            None
+        );
+        assert_eq!(
+            VaultPaymentMethodKind::BankRedirect.to_string(),
+            "bank_redirect"
+        );
+    }
+
+    #[test]
+    fn test_vault_operation_limiter_rejects_beyond_limit() {
+        let limiter: &'static VaultOperationLimiter =
+            Box::leak(Box::new(VaultOperationLimiter::new(1)));
+
+        let permit = limiter.try_acquire().unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(matches!(
+            limiter
+                .try_acquire()
+                .map(|_| ())
+                .unwrap_err()
+                .current_context(),
+            errors::VaultError::BackendBusy
+        ));
+
+        drop(permit);
+        assert_eq!(limiter.in_flight(), 0);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn test_derived_lookup_key_is_stable_per_merchant() {
+        let hash_key = b"merchant_key";
+        let lookup_key = VaultKeyKind::PaymentToken
+            .derive_lookup_key(hash_key, "merchant_1", "request_1")
+            .unwrap();
+
+        assert!(lookup_key.starts_with("token_"));
+        assert_eq!(
+            VaultKeyKind::PaymentToken.derive_lookup_key(hash_key, "merchant_1", "request_1"),
+            Some(lookup_key.clone())
+        );
+        assert_ne!(
+            VaultKeyKind::PaymentToken.derive_lookup_key(hash_key, "merchant_2", "request_1"),
+            Some(lookup_key.clone())
+        );
+        assert_ne!(
+            VaultKeyKind::PaymentToken.derive_lookup_key(hash_key, "merchant_1", "request_2"),
+            Some(lookup_key)
+        );
+    }
+
+    #[test]
+    fn test_verify_token_payment_method() {
+        let payment_method = api::PaymentMethodData::Reward;
+
+        assert!(verify_token_payment_method(&payment_method, None).is_ok());
+        assert!(
+            verify_token_payment_method(&payment_method, Some(enums::PaymentMethod::Reward))
+                .is_ok()
+        );
+        assert!(matches!(
+            verify_token_payment_method(&payment_method, Some(enums::PaymentMethod::Card))
+                .unwrap_err()
+                .current_context(),
+            errors::VaultError::TokenMethodMismatch
+        ));
+    }
+}
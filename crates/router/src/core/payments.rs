@@ -2500,6 +2500,8 @@ where
     pub authentication: Option<storage::Authentication>,
     pub recurring_details: Option<RecurringDetails>,
     pub poll_config: Option<router_types::PollConfig>,
+    /// Amount to reverse when only a part of the authorization is voided
+    pub reversal_amount: Option<MinorUnit>,
 }
 
 #[derive(Clone, serde::Serialize, Debug)]
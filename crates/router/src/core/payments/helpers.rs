@@ -1681,7 +1681,7 @@ pub async fn retrieve_payment_method_with_temporary_token(
     card_token_data: Option<&CardToken>,
 ) -> RouterResult<Option<(api::PaymentMethodData, enums::PaymentMethod)>> {
     let (pm, supplementary_data) =
-        vault::Vault::get_payment_method_data_from_locker(state, token, merchant_key_store)
+        vault::Vault::get_payment_method_data_from_locker(state, token, merchant_key_store, None)
             .await
             .attach_printable(
                 "Payment method for given token not found or there was a problem fetching it",
@@ -1735,10 +1735,14 @@ pub async fn retrieve_payment_method_with_temporary_token(
                 vault::Vault::store_payment_method_data_in_locker(
                     state,
                     Some(token.to_owned()),
+                    None,
                     &updated_pm,
                     payment_intent.customer_id.to_owned(),
                     enums::PaymentMethod::Card,
                     merchant_key_store,
+                    true,
+                    supplementary_data.billing_address.as_ref(),
+                    supplementary_data.payment_method_id.clone(),
                 )
                 .await?;
 
@@ -2005,6 +2009,7 @@ pub async fn make_pm_data<'a, F: Clone, R>(
                 &payment_data.payment_intent,
                 &payment_data.payment_attempt,
                 merchant_key_store,
+                payment_data.address.get_payment_method_billing(),
             )
             .await?;
 
@@ -2025,14 +2030,19 @@ pub async fn store_in_vault_and_generate_ppmt(
     payment_attempt: &PaymentAttempt,
     payment_method: enums::PaymentMethod,
     merchant_key_store: &domain::MerchantKeyStore,
+    billing_address: Option<&api_models::payments::Address>,
 ) -> RouterResult<String> {
     let router_token = vault::Vault::store_payment_method_data_in_locker(
         state,
         None,
+        None,
         payment_method_data,
         payment_intent.customer_id.to_owned(),
         payment_method,
         merchant_key_store,
+        true,
+        billing_address,
+        None,
     )
     .await?;
     let parent_payment_method_token = generate_id(consts::ID_LENGTH, "token");
@@ -2061,6 +2071,7 @@ pub async fn store_payment_method_data_in_vault(
     payment_method: enums::PaymentMethod,
     payment_method_data: &api::PaymentMethodData,
     merchant_key_store: &domain::MerchantKeyStore,
+    billing_address: Option<&api_models::payments::Address>,
 ) -> RouterResult<Option<String>> {
     if should_store_payment_method_data_in_vault(
         &state.conf.temp_locker_enable_config,
@@ -2075,6 +2086,7 @@ pub async fn store_payment_method_data_in_vault(
             payment_attempt,
             payment_method,
             merchant_key_store,
+            billing_address,
         )
         .await?;
 
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use api_models::enums::FrmSuggestion;
 use async_trait::async_trait;
-use common_utils::ext_traits::AsyncExt;
+use common_utils::{ext_traits::AsyncExt, types::MinorUnit};
 use error_stack::ResultExt;
 use router_derive;
 use router_env::{instrument, tracing};
@@ -106,6 +106,10 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
         )
         .await?;
 
+        if let Some(amount_to_reverse) = request.amount_to_reverse {
+            validate_partial_reversal(state, &payment_attempt, amount_to_reverse)?;
+        }
+
         let currency = payment_attempt.currency.get_required_value("currency")?;
         let amount = payment_attempt.get_total_amount().into();
 
@@ -188,6 +192,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsCancelRequest>
             authentication: None,
             recurring_details: None,
             poll_config: None,
+            reversal_amount: request.amount_to_reverse,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -281,6 +286,15 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsCancelRequest> for Payment
         BoxedOperation<'b, F, api::PaymentsCancelRequest>,
         operations::ValidateResult<'a>,
     )> {
+        if request
+            .amount_to_reverse
+            .is_some_and(|amount_to_reverse| amount_to_reverse <= MinorUnit::new(0))
+        {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "amount_to_reverse must be greater than zero".to_string(),
+            })?
+        }
+
         Ok((
             Box::new(self),
             operations::ValidateResult {
@@ -292,3 +306,41 @@ impl<F: Send + Clone> ValidateRequest<F, api::PaymentsCancelRequest> for Payment
         ))
     }
 }
+
+/// Only connectors which support partial voids reverse a part of the authorization, the others
+/// would silently void the whole authorization
+fn validate_partial_reversal(
+    state: &SessionState,
+    payment_attempt: &storage::PaymentAttempt,
+    amount_to_reverse: MinorUnit,
+) -> RouterResult<()> {
+    let connector_name = payment_attempt
+        .connector
+        .as_ref()
+        .get_required_value("connector")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Connector not set in payment attempt")?;
+    let connector = api::ConnectorData::get_connector_by_name(
+        &state.conf.connectors,
+        connector_name,
+        api::GetToken::Connector,
+        payment_attempt.merchant_connector_id.clone(),
+    )?;
+
+    let supports_partial_void = connector
+        .connector
+        .get_connector_specifications()
+        .is_some_and(|specifications| specifications.supports_partial_void);
+    if !supports_partial_void {
+        Err(errors::ApiErrorResponse::NotSupported {
+            message: format!("Partial reversal is not supported by {connector_name}"),
+        })?
+    }
+    if amount_to_reverse > payment_attempt.amount_capturable {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "amount_to_reverse must not exceed the amount capturable".to_string(),
+        })?
+    }
+
+    Ok(())
+}
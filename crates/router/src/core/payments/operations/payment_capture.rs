@@ -234,6 +234,7 @@ impl<F: Send + Clone> GetTracker<F, payments::PaymentData<F>, api::PaymentsCaptu
             authentication: None,
             recurring_details: None,
             poll_config: None,
+            reversal_amount: None,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
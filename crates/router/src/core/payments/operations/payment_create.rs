@@ -451,6 +451,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, api::PaymentsRequest> for Pa
             authentication: None,
             recurring_details,
             poll_config: None,
+            reversal_amount: None,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -172,6 +172,7 @@ impl<F: Send + Clone> GetTracker<F, PaymentData<F>, PaymentsCancelRequest> for P
             authentication: None,
             recurring_details: None,
             poll_config: None,
+            reversal_amount: None,
         };
 
         let get_trackers_response = operations::GetTrackerResponse {
@@ -491,6 +491,37 @@ impl<F: Clone> PostUpdateTracker<F, PaymentData<F>, types::PaymentsCancelData> f
         ))
         .await?;
 
+        // A partially reversed authorization stays authorized for the remaining amount only
+        if let (Some(reversal_amount), enums::AttemptStatus::Authorized) = (
+            payment_data.reversal_amount,
+            payment_data.payment_attempt.status,
+        ) {
+            let remaining_amount = payment_data.payment_attempt.amount - reversal_amount;
+            payment_data.payment_attempt = db
+                .store
+                .update_payment_attempt_with_attempt_id(
+                    payment_data.payment_attempt.clone(),
+                    storage::PaymentAttemptUpdate::IncrementalAuthorizationAmountUpdate {
+                        amount: remaining_amount,
+                        amount_capturable: remaining_amount,
+                    },
+                    storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+            payment_data.payment_intent = db
+                .store
+                .update_payment_intent(
+                    payment_data.payment_intent.clone(),
+                    storage::PaymentIntentUpdate::IncrementalAuthorizationAmountUpdate {
+                        amount: remaining_amount,
+                    },
+                    storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+        }
+
         Ok(payment_data)
     }
 }
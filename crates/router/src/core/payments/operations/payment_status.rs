@@ -473,6 +473,7 @@ async fn get_tracker_for_sync<
         authentication,
         recurring_details: None,
         poll_config: None,
+        reversal_amount: None,
     };
 
     let get_trackers_response = operations::GetTrackerResponse {
@@ -44,6 +44,7 @@ use crate::{
         routing::{self, helpers as routing_helpers},
     },
     logger,
+    routes::metrics,
     types::{
         api::{self, routing as routing_types},
         domain, storage as oss_storage,
@@ -660,9 +661,13 @@ pub async fn refresh_cgraph_cache<'a>(
         default_configs,
     };
     let cgraph = Arc::new(
-        mca_graph::make_mca_graph(api_mcas, &config_pm_filters)
-            .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
-            .attach_printable("when construction cgraph")?,
+        metrics::request::record_constraint_graph_build(
+            "mca_graph",
+            || mca_graph::make_mca_graph(api_mcas, &config_pm_filters),
+            |result| result.as_ref().ok(),
+        )
+        .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
+        .attach_printable("when construction cgraph")?,
     );
 
     CGRAPH_CACHE
@@ -1482,6 +1482,9 @@ impl<F: Clone> TryFrom<PaymentAdditionalData<'_, F>> for types::PaymentsCancelDa
         let amount = MinorUnit::from(payment_data.amount);
         Ok(Self {
             amount: Some(amount.get_amount_as_i64()), // This should be removed once we start moving to connector module
+            reversal_amount: payment_data
+                .reversal_amount
+                .map(|amount| amount.get_amount_as_i64()),
             currency: Some(payment_data.currency),
             connector_transaction_id: connector
                 .connector
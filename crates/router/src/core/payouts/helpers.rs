@@ -108,7 +108,7 @@ pub async fn make_payout_method_data<'a>(
         payout_data,
     ) {
         // Get operation
-        (None, Some(payout_token), _) => {
+        (None, Some(payout_token), payout_data) => {
             if payout_token.starts_with("temporary_token_")
                 || payout_type == Some(api_enums::PayoutType::Bank)
             {
@@ -129,6 +129,19 @@ pub async fn make_payout_method_data<'a>(
                         Err(errors::ApiErrorResponse::PreconditionFailed { message: "customer associated with payout method and customer passed in payout are not same".into() })
                     },
                 )?;
+                if let (Some(api::PayoutMethodData::Wallet(wallet)), Some(payout_data)) =
+                    (pm.as_ref(), payout_data)
+                {
+                    vault::validate_wallet_payout_currency(
+                        wallet,
+                        payout_data.payouts.destination_currency,
+                    )
+                    .change_context(
+                        errors::ApiErrorResponse::InvalidRequestData {
+                            message: "Payout currency is not supported by the wallet".to_string(),
+                        },
+                    )?;
+                }
                 Ok(pm)
             } else {
                 let resp = cards::get_card_from_locker(
@@ -1272,6 +1272,7 @@ pub async fn list_merchants_for_user(
                 .iter()
                 .map(|role| role.merchant_id.clone())
                 .collect(),
+            state.conf.merchant_key_store.decryption_concurrency,
         )
         .await
         .change_context(UserErrors::InternalServerError)?;
@@ -1736,6 +1737,7 @@ pub async fn verify_totp(
     state: SessionState,
     user_token: auth::UserIdFromAuth,
     req: user_api::VerifyTotpRequest,
+    req_state: ReqState,
 ) -> UserResponse<user_api::TokenResponse> {
     let user_from_db: domain::UserFromStorage = state
         .global_store
@@ -1753,21 +1755,17 @@ pub async fn verify_totp(
         .await?
         .ok_or(UserErrors::InternalServerError)?;
 
-    let totp = tfa_utils::generate_default_totp(
+    if !tfa_utils::verify_totp(
         user_from_db.get_email(),
-        Some(user_totp_secret),
+        user_totp_secret,
+        &req.totp,
         state.conf.user.totp_issuer_name.clone(),
-    )?;
-
-    if totp
-        .generate_current()
-        .change_context(UserErrors::InternalServerError)?
-        != req.totp.expose()
-    {
+        consts::user::TOTP_TOLERANCE,
+    )? {
         return Err(UserErrors::InvalidTotp.into());
     }
 
-    tfa_utils::insert_totp_in_redis(&state, &user_token.user_id).await?;
+    tfa_utils::record_totp_success(&state, &req_state, &user_token.user_id).await?;
 
     Ok(ApplicationResponse::StatusOk)
 }
@@ -1776,6 +1774,7 @@ pub async fn update_totp(
     state: SessionState,
     user_token: auth::UserIdFromAuth,
     req: user_api::VerifyTotpRequest,
+    req_state: ReqState,
 ) -> UserResponse<()> {
     let user_from_db: domain::UserFromStorage = state
         .global_store
@@ -1788,20 +1787,22 @@ pub async fn update_totp(
         .await?
         .ok_or(UserErrors::TotpSecretNotFound)?;
 
+    if !tfa_utils::verify_totp(
+        user_from_db.get_email(),
+        new_totp_secret.clone(),
+        &req.totp,
+        state.conf.user.totp_issuer_name.clone(),
+        consts::user::TOTP_TOLERANCE,
+    )? {
+        return Err(UserErrors::InvalidTotp.into());
+    }
+
     let totp = tfa_utils::generate_default_totp(
         user_from_db.get_email(),
         Some(new_totp_secret),
         state.conf.user.totp_issuer_name.clone(),
     )?;
 
-    if totp
-        .generate_current()
-        .change_context(UserErrors::InternalServerError)?
-        != req.totp.expose()
-    {
-        return Err(UserErrors::InvalidTotp.into());
-    }
-
     let key_store = user_from_db.get_or_create_key_store(&state).await?;
 
     state
@@ -1834,7 +1835,7 @@ pub async fn update_totp(
     // This is not the main task of this API, so we don't throw error if this fails.
     // Any following API which requires TOTP will throw error if TOTP is not set in redis
     // and FE will ask user to enter TOTP again
-    let _ = tfa_utils::insert_totp_in_redis(&state, &user_token.user_id)
+    let _ = tfa_utils::record_totp_success(&state, &req_state, &user_token.user_id)
         .await
         .map_err(|e| logger::error!(?e));
 
@@ -869,9 +869,10 @@ impl MerchantAccountInterface for KafkaStore {
     async fn list_multiple_merchant_accounts(
         &self,
         merchant_ids: Vec<String>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
         self.diesel_store
-            .list_multiple_merchant_accounts(merchant_ids)
+            .list_multiple_merchant_accounts(merchant_ids, decryption_concurrency)
             .await
     }
 }
@@ -1839,6 +1840,17 @@ impl ProcessTrackerInterface for KafkaStore {
             .find_processes_by_time_status(time_lower_limit, time_upper_limit, status, limit)
             .await
     }
+
+    async fn find_processes_by_runner_business_status(
+        &self,
+        runner: storage::ProcessTrackerRunner,
+        business_status: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_processes_by_runner_business_status(runner, business_status, limit)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -2072,9 +2084,20 @@ impl MerchantKeyStoreInterface for KafkaStore {
         &self,
         merchant_ids: Vec<String>,
         key: &Secret<Vec<u8>>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantKeyStore>, errors::StorageError> {
         self.diesel_store
-            .list_multiple_key_stores(merchant_ids, key)
+            .list_multiple_key_stores(merchant_ids, key, decryption_concurrency)
+            .await
+    }
+
+    #[cfg(feature = "olap")]
+    async fn list_key_store_metadata(
+        &self,
+        merchant_ids: Vec<String>,
+    ) -> CustomResult<Vec<(String, PrimitiveDateTime)>, errors::StorageError> {
+        self.diesel_store
+            .list_key_store_metadata(merchant_ids)
             .await
     }
 }
@@ -80,6 +80,7 @@ where
     async fn list_multiple_merchant_accounts(
         &self,
         merchant_ids: Vec<String>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError>;
 }
 
@@ -315,6 +316,7 @@ impl MerchantAccountInterface for Store {
     async fn list_multiple_merchant_accounts(
         &self,
         merchant_ids: Vec<String>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
         let conn = connection::pg_connection_read(self).await?;
 
@@ -333,6 +335,7 @@ impl MerchantAccountInterface for Store {
                     .cloned()
                     .collect(),
                 &db_master_key,
+                decryption_concurrency,
             )
             .await?;
 
@@ -498,6 +501,7 @@ impl MerchantAccountInterface for MockDb {
     async fn list_multiple_merchant_accounts(
         &self,
         _merchant_ids: Vec<String>,
+        _decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantAccount>, errors::StorageError> {
         Err(errors::StorageError::MockDbError)?
     }
@@ -508,9 +512,19 @@ async fn publish_and_redact_merchant_account_cache(
     store: &dyn super::StorageInterface,
     merchant_account: &storage::MerchantAccount,
 ) -> CustomResult<(), errors::StorageError> {
+    let cache_keys = get_merchant_account_cache_keys(merchant_account);
+    cache::publish_into_redact_channel(store.get_cache_store().as_ref(), cache_keys).await?;
+    Ok(())
+}
+
+/// Keys of the caches which hold the merchant account or data derived from it
+#[cfg(feature = "accounts_cache")]
+pub(super) fn get_merchant_account_cache_keys(
+    merchant_account: &storage::MerchantAccount,
+) -> Vec<CacheKind<'static>> {
     let publishable_key = merchant_account
         .publishable_key
-        .as_ref()
+        .clone()
         .map(|publishable_key| CacheKind::Accounts(publishable_key.into()));
 
     #[cfg(feature = "business_profile_routing")]
@@ -531,14 +545,13 @@ async fn publish_and_redact_merchant_account_cache(
     ));
 
     let mut cache_keys = vec![CacheKind::Accounts(
-        merchant_account.merchant_id.as_str().into(),
+        merchant_account.merchant_id.clone().into(),
     )];
 
     cache_keys.extend(publishable_key.into_iter());
     cache_keys.extend(cgraph_key.into_iter());
 
-    cache::publish_into_redact_channel(store.get_cache_store().as_ref(), cache_keys).await?;
-    Ok(())
+    cache_keys
 }
 
 #[cfg(feature = "accounts_cache")]
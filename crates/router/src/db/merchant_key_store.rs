@@ -1,9 +1,13 @@
 use error_stack::{report, ResultExt};
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 use router_env::{instrument, tracing};
 #[cfg(feature = "accounts_cache")]
 use storage_impl::redis::cache::{self, CacheKind, ACCOUNTS_CACHE};
+#[cfg(feature = "olap")]
+use time::PrimitiveDateTime;
 
+#[cfg(feature = "accounts_cache")]
+use crate::types::storage;
 use crate::{
     connection,
     core::errors::{self, CustomResult},
@@ -34,12 +38,23 @@ pub trait MerchantKeyStoreInterface {
         merchant_id: &str,
     ) -> CustomResult<bool, errors::StorageError>;
 
+    /// Lists the key stores of the given merchants, decrypting at most `decryption_concurrency`
+    /// of them at a time
     #[cfg(feature = "olap")]
     async fn list_multiple_key_stores(
         &self,
         merchant_ids: Vec<String>,
         key: &Secret<Vec<u8>>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantKeyStore>, errors::StorageError>;
+
+    /// Lists the merchant id and creation time of the key stores, for audits which have no need
+    /// to decrypt the keys
+    #[cfg(feature = "olap")]
+    async fn list_key_store_metadata(
+        &self,
+        merchant_ids: Vec<String>,
+    ) -> CustomResult<Vec<(String, PrimitiveDateTime)>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -81,16 +96,10 @@ impl MerchantKeyStoreInterface for Store {
         };
 
         #[cfg(not(feature = "accounts_cache"))]
-        {
-            fetch_func()
-                .await?
-                .convert(key)
-                .await
-                .change_context(errors::StorageError::DecryptionError)
-        }
+        let merchant_key_store = fetch_func().await?;
 
         #[cfg(feature = "accounts_cache")]
-        {
+        let merchant_key_store = {
             let key_store_cache_key = format!("merchant_key_store_{}", merchant_id);
             cache::get_or_populate_in_memory(
                 self,
@@ -99,10 +108,14 @@ impl MerchantKeyStoreInterface for Store {
                 &ACCOUNTS_CACHE,
             )
             .await?
+        };
+
+        let ciphertext_len = merchant_key_store.key.get_inner().peek().len();
+        merchant_key_store
             .convert(key)
             .await
             .change_context(errors::StorageError::DecryptionError)
-        }
+            .attach_printable_lazy(|| get_decryption_failure_reason(ciphertext_len))
     }
 
     #[instrument(skip_all)]
@@ -127,13 +140,30 @@ impl MerchantKeyStoreInterface for Store {
 
         #[cfg(feature = "accounts_cache")]
         {
-            let key_store_cache_key = format!("merchant_key_store_{}", merchant_id);
-            cache::publish_and_redact(
-                self,
-                CacheKind::Accounts(key_store_cache_key.into()),
-                delete_func,
-            )
-            .await
+            let conn = connection::pg_connection_read(self).await?;
+            // The merchant account is usually deleted before its key store, in which case its own
+            // deletion has already redacted its cache keys
+            let merchant_account =
+                match storage::MerchantAccount::find_by_merchant_id(&conn, merchant_id)
+                    .await
+                    .map_err(|error| report!(errors::StorageError::from(error)))
+                {
+                    Ok(merchant_account) => Some(merchant_account),
+                    Err(error) if error.current_context().is_db_not_found() => None,
+                    Err(error) => return Err(error),
+                };
+            let merchant_connector_accounts =
+                storage::MerchantConnectorAccount::find_by_merchant_id(&conn, merchant_id, true)
+                    .await
+                    .map_err(|error| report!(errors::StorageError::from(error)))?;
+
+            let cache_keys = get_dependent_account_cache_keys(
+                merchant_id,
+                merchant_account.as_ref(),
+                &merchant_connector_accounts,
+            );
+
+            cache::publish_and_redact_multiple(self, cache_keys, delete_func).await
         }
     }
 
@@ -143,6 +173,7 @@ impl MerchantKeyStoreInterface for Store {
         &self,
         merchant_ids: Vec<String>,
         key: &Secret<Vec<u8>>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantKeyStore>, errors::StorageError> {
         let fetch_func = || async {
             let conn = connection::pg_connection_read(self).await?;
@@ -155,13 +186,22 @@ impl MerchantKeyStoreInterface for Store {
             .map_err(|error| report!(errors::StorageError::from(error)))
         };
 
-        futures::future::try_join_all(fetch_func().await?.into_iter().map(|key_store| async {
-            key_store
-                .convert(key)
-                .await
-                .change_context(errors::StorageError::DecryptionError)
-        }))
+        decrypt_key_stores(fetch_func().await?, key, decryption_concurrency).await
+    }
+
+    #[cfg(feature = "olap")]
+    #[instrument(skip_all)]
+    async fn list_key_store_metadata(
+        &self,
+        merchant_ids: Vec<String>,
+    ) -> CustomResult<Vec<(String, PrimitiveDateTime)>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        diesel_models::merchant_key_store::MerchantKeyStore::list_key_store_metadata(
+            &conn,
+            merchant_ids,
+        )
         .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
     }
 }
 
@@ -235,22 +275,106 @@ impl MerchantKeyStoreInterface for MockDb {
         &self,
         merchant_ids: Vec<String>,
         key: &Secret<Vec<u8>>,
+        decryption_concurrency: usize,
     ) -> CustomResult<Vec<domain::MerchantKeyStore>, errors::StorageError> {
-        let merchant_key_stores = self.merchant_key_store.lock().await;
-        futures::future::try_join_all(
-            merchant_key_stores
-                .iter()
-                .filter(|merchant_key| merchant_ids.contains(&merchant_key.merchant_id))
-                .map(|merchant_key| async {
-                    merchant_key
-                        .to_owned()
-                        .convert(key)
-                        .await
-                        .change_context(errors::StorageError::DecryptionError)
-                }),
-        )
+        let merchant_key_stores = self
+            .merchant_key_store
+            .lock()
+            .await
+            .iter()
+            .filter(|merchant_key| merchant_ids.contains(&merchant_key.merchant_id))
+            .cloned()
+            .collect();
+        decrypt_key_stores(merchant_key_stores, key, decryption_concurrency).await
+    }
+
+    #[cfg(feature = "olap")]
+    async fn list_key_store_metadata(
+        &self,
+        merchant_ids: Vec<String>,
+    ) -> CustomResult<Vec<(String, PrimitiveDateTime)>, errors::StorageError> {
+        Ok(self
+            .merchant_key_store
+            .lock()
+            .await
+            .iter()
+            .filter(|merchant_key| merchant_ids.contains(&merchant_key.merchant_id))
+            .map(|merchant_key| (merchant_key.merchant_id.clone(), merchant_key.created_at))
+            .collect())
+    }
+}
+
+/// Decrypts the key stores in order, with at most `decryption_concurrency` decryptions in flight so
+/// that listing thousands of merchants does not starve the runtime. The first key store which
+/// fails to decrypt fails the whole listing.
+#[cfg(feature = "olap")]
+async fn decrypt_key_stores(
+    merchant_key_stores: Vec<diesel_models::merchant_key_store::MerchantKeyStore>,
+    key: &Secret<Vec<u8>>,
+    decryption_concurrency: usize,
+) -> CustomResult<Vec<domain::MerchantKeyStore>, errors::StorageError> {
+    use futures::{StreamExt, TryStreamExt};
+
+    futures::stream::iter(merchant_key_stores)
+        .map(|merchant_key_store| async move {
+            merchant_key_store
+                .convert(key)
+                .await
+                .change_context(errors::StorageError::DecryptionError)
+        })
+        .buffered(decryption_concurrency.max(1))
+        .try_collect()
         .await
+}
+
+/// AES-GCM ciphertexts carry a nonce and an authentication tag, so a payload shorter than both is
+/// malformed, while a failure on a complete payload means the tag did not verify, either because
+/// the key is wrong or because the ciphertext is corrupted
+fn get_decryption_failure_reason(ciphertext_len: usize) -> &'static str {
+    let min_ciphertext_len = ring::aead::NONCE_LEN + ring::aead::AES_256_GCM.tag_len();
+    if ciphertext_len < min_ciphertext_len {
+        "Merchant key store ciphertext is malformed"
+    } else {
+        "Merchant key store tag mismatch (wrong key or corrupted ciphertext)"
+    }
+}
+
+/// Keys of the caches which hold data decrypted with the merchant's key store, and which must
+/// therefore be redacted along with the key store itself
+#[cfg(feature = "accounts_cache")]
+fn get_dependent_account_cache_keys(
+    merchant_id: &str,
+    merchant_account: Option<&storage::MerchantAccount>,
+    merchant_connector_accounts: &[storage::MerchantConnectorAccount],
+) -> Vec<CacheKind<'static>> {
+    let mut cache_keys = vec![CacheKind::Accounts(
+        format!("merchant_key_store_{}", merchant_id).into(),
+    )];
+
+    match merchant_account {
+        Some(merchant_account) => cache_keys.extend(
+            super::merchant_account::get_merchant_account_cache_keys(merchant_account),
+        ),
+        None => cache_keys.push(CacheKind::Accounts(merchant_id.to_string().into())),
+    }
+
+    for mca in merchant_connector_accounts {
+        cache_keys.push(CacheKind::Accounts(
+            format!("{}_{}", mca.merchant_id, mca.merchant_connector_id).into(),
+        ));
+        if let Some(connector_label) = &mca.connector_label {
+            cache_keys.push(CacheKind::Accounts(
+                format!("{}_{}", mca.merchant_id, connector_label).into(),
+            ));
+        }
+        if let Some(profile_id) = &mca.profile_id {
+            cache_keys.push(CacheKind::Accounts(
+                format!("{}_{}", profile_id, mca.connector_name).into(),
+            ));
+        }
     }
+
+    cache_keys
 }
 
 #[cfg(test)]
@@ -325,4 +449,211 @@ mod tests {
             .await;
         assert!(find_merchant_key_with_incorrect_master_key_result.is_err());
     }
+
+    #[cfg(feature = "olap")]
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mock_db_list_key_store_metadata() {
+        #[allow(clippy::expect_used)]
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let master_key = mock_db.get_master_key();
+
+        for merchant_id in ["merchant1", "merchant2"] {
+            mock_db
+                .insert_merchant_key_store(
+                    domain::MerchantKeyStore {
+                        merchant_id: merchant_id.into(),
+                        key: domain::types::encrypt(
+                            services::generate_aes256_key().unwrap().to_vec().into(),
+                            master_key,
+                        )
+                        .await
+                        .unwrap(),
+                        created_at: datetime!(2023-02-01 0:00),
+                    },
+                    &master_key.to_vec().into(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let key_store_metadata = mock_db
+            .list_key_store_metadata(vec!["merchant1".to_string(), "non_existent".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            key_store_metadata,
+            vec![("merchant1".to_string(), datetime!(2023-02-01 0:00))]
+        );
+    }
+
+    #[cfg(feature = "olap")]
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mock_db_list_multiple_key_stores_with_bounded_concurrency() {
+        #[allow(clippy::expect_used)]
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let master_key = mock_db.get_master_key();
+        let merchant_ids = (0..200)
+            .map(|index| format!("merchant{index}"))
+            .collect::<Vec<_>>();
+
+        for merchant_id in &merchant_ids {
+            mock_db
+                .insert_merchant_key_store(
+                    domain::MerchantKeyStore {
+                        merchant_id: merchant_id.clone(),
+                        key: domain::types::encrypt(
+                            services::generate_aes256_key().unwrap().to_vec().into(),
+                            master_key,
+                        )
+                        .await
+                        .unwrap(),
+                        created_at: datetime!(2023-02-01 0:00),
+                    },
+                    &master_key.to_vec().into(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let key_stores = mock_db
+            .list_multiple_key_stores(merchant_ids.clone(), &master_key.to_vec().into(), 8)
+            .await
+            .unwrap();
+        assert_eq!(
+            key_stores
+                .into_iter()
+                .map(|key_store| key_store.merchant_id)
+                .collect::<Vec<_>>(),
+            merchant_ids
+        );
+
+        let list_with_incorrect_master_key_result = mock_db
+            .list_multiple_key_stores(merchant_ids, &vec![0; 32].into(), 8)
+            .await;
+        assert!(list_with_incorrect_master_key_result.is_err());
+    }
+
+    #[cfg(feature = "accounts_cache")]
+    #[test]
+    fn test_dependent_account_cache_keys() {
+        use storage_impl::redis::cache::CacheKind;
+
+        let merchant_connector_account = crate::types::storage::MerchantConnectorAccount {
+            id: 1,
+            merchant_id: "merchant1".to_string(),
+            connector_name: "stripe".to_string(),
+            connector_account_details: diesel_models::encryption::Encryption::new(
+                Vec::<u8>::new().into(),
+            ),
+            test_mode: None,
+            disabled: None,
+            merchant_connector_id: "mca1".to_string(),
+            payment_methods_enabled: None,
+            connector_type: diesel_models::enums::ConnectorType::PaymentProcessor,
+            metadata: None,
+            connector_label: Some("stripe_US_default".to_string()),
+            business_country: None,
+            business_label: None,
+            business_sub_label: None,
+            frm_configs: None,
+            created_at: datetime!(2023-02-01 0:00),
+            modified_at: datetime!(2023-02-01 0:00),
+            connector_webhook_details: None,
+            frm_config: None,
+            profile_id: Some("pro1".to_string()),
+            applepay_verified_domains: None,
+            pm_auth_config: None,
+            status: diesel_models::enums::ConnectorStatus::Active,
+            connector_wallets_details: None,
+        };
+
+        let merchant_account = crate::types::storage::MerchantAccount {
+            id: 1,
+            merchant_id: "merchant1".to_string(),
+            return_url: None,
+            enable_payment_response_hash: false,
+            payment_response_hash_key: None,
+            redirect_to_merchant_with_http_post: false,
+            merchant_name: None,
+            merchant_details: None,
+            webhook_details: None,
+            sub_merchants_enabled: None,
+            parent_merchant_id: None,
+            publishable_key: Some("pk_merchant1".to_string()),
+            storage_scheme: diesel_models::enums::MerchantStorageScheme::PostgresOnly,
+            locker_id: None,
+            metadata: None,
+            routing_algorithm: None,
+            primary_business_details: serde_json::json!([]),
+            intent_fulfillment_time: None,
+            created_at: datetime!(2023-02-01 0:00),
+            modified_at: datetime!(2023-02-01 0:00),
+            frm_routing_algorithm: None,
+            payout_routing_algorithm: None,
+            organization_id: "org1".to_string(),
+            is_recon_enabled: false,
+            default_profile: Some("pro1".to_string()),
+            recon_status: diesel_models::enums::ReconStatus::NotRequested,
+            payment_link_config: None,
+        };
+
+        let cache_key_name = |kind: CacheKind<'_>| match kind {
+            CacheKind::Accounts(key) => format!("accounts,{key}"),
+            CacheKind::CGraph(key) => format!("cgraph,{key}"),
+            _ => "unexpected".to_string(),
+        };
+        #[cfg(feature = "business_profile_routing")]
+        let cgraph_key = "cgraph,cgraph_merchant1_pro1";
+        #[cfg(not(feature = "business_profile_routing"))]
+        let cgraph_key = "cgraph,cgraph_merchant1";
+
+        let cache_keys = super::get_dependent_account_cache_keys(
+            "merchant1",
+            Some(&merchant_account),
+            std::slice::from_ref(&merchant_connector_account),
+        )
+        .into_iter()
+        .map(cache_key_name)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            cache_keys,
+            vec![
+                "accounts,merchant_key_store_merchant1",
+                "accounts,merchant1",
+                "accounts,pk_merchant1",
+                cgraph_key,
+                "accounts,merchant1_mca1",
+                "accounts,merchant1_stripe_US_default",
+                "accounts,pro1_stripe",
+            ]
+        );
+
+        let cache_keys_without_merchant_account = super::get_dependent_account_cache_keys(
+            "merchant1",
+            None,
+            &[merchant_connector_account],
+        )
+        .into_iter()
+        .map(cache_key_name)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            cache_keys_without_merchant_account,
+            vec![
+                "accounts,merchant_key_store_merchant1",
+                "accounts,merchant1",
+                "accounts,merchant1_mca1",
+                "accounts,merchant1_stripe_US_default",
+                "accounts,pro1_stripe",
+            ]
+        );
+    }
 }
@@ -27,6 +27,16 @@ pub enum AuditEventType {
         capture_amount: Option<MinorUnit>,
         multiple_capture_count: Option<i16>,
     },
+    TwoFactorAuthSuccess {
+        user_id: String,
+        method: TwoFactorAuthMethod,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TwoFactorAuthMethod {
+    Totp,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -65,6 +75,7 @@ impl Event for AuditEvent {
             AuditEventType::RefundSuccess => "refund_success",
             AuditEventType::RefundFail => "refund_fail",
             AuditEventType::PaymentCancelled { .. } => "payment_cancelled",
+            AuditEventType::TwoFactorAuthSuccess { .. } => "two_factor_auth_success",
         };
         format!(
             "{event_type}-{}",
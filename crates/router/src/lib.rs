@@ -183,6 +183,9 @@ pub fn mk_app(
 #[allow(clippy::expect_used, clippy::unwrap_used)]
 pub async fn start_server(conf: settings::Settings<SecuredSecret>) -> ApplicationResult<Server> {
     logger::debug!(startup_config=?conf);
+    api_models::payment_methods::set_tokenized_data_redaction_policy(
+        conf.locker.tokenized_data_redaction_policy,
+    );
     let server = conf.server.clone();
     let (tx, rx) = oneshot::channel();
     let api_client = Box::new(
@@ -190,6 +190,56 @@ pub async fn delete_merchant_account(
     )
     .await
 }
+/// Merchant Account - Purge Tokens
+///
+/// Delete all the temporary locker tokens of the Merchant Account, when offboarding the merchant
+#[instrument(skip_all, fields(flow = ?Flow::MerchantTokensPurge))]
+pub async fn merchant_account_purge_tokens(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    mid: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantTokensPurge;
+    let mid = mid.into_inner();
+
+    let payload = web::Json(admin::MerchantId { merchant_id: mid }).into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req, _| purge_merchant_tokens(state, req.merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Token Counts
+///
+/// Count the temporary locker tokens of the Merchant Account by the kind of payment method they hold
+#[instrument(skip_all, fields(flow = ?Flow::MerchantTokenCounts))]
+pub async fn merchant_account_token_counts(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    mid: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantTokenCounts;
+    let mid = mid.into_inner();
+
+    let payload = web::Json(admin::MerchantId { merchant_id: mid }).into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req, _| get_merchant_token_counts(state, req.merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
 /// Merchant Connector - Create
 ///
 /// Create a new Merchant Connector for the merchant account. The connector could be a payment processor / facilitator / acquirer or specialized services like Fraud / Accounting etc."
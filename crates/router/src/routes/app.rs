@@ -1006,6 +1006,14 @@ impl MerchantAccount {
                     .route(web::get().to(merchant_account_kv_status)),
             )
             .service(web::resource("/kv").route(web::post().to(merchant_account_toggle_all_kv)))
+            .service(
+                web::resource("/{id}/purge_tokens")
+                    .route(web::post().to(merchant_account_purge_tokens)),
+            )
+            .service(
+                web::resource("/{id}/token_counts")
+                    .route(web::get().to(merchant_account_token_counts)),
+            )
             .service(
                 web::resource("/{id}")
                     .route(web::get().to(retrieve_merchant_account))
@@ -44,7 +44,13 @@ counter_metric!(CREATED_TOKENIZED_CARD, GLOBAL_METER);
 counter_metric!(DELETED_TOKENIZED_CARD, GLOBAL_METER);
 counter_metric!(GET_TOKENIZED_CARD, GLOBAL_METER);
 counter_metric!(TOKENIZED_DATA_COUNT, GLOBAL_METER); // Tokenized data added
+counter_metric!(TOKENIZED_DATA_WITHOUT_DELETE_TASK_COUNT, GLOBAL_METER); // Tokenized data added without a delete task
 counter_metric!(RETRIED_DELETE_DATA_COUNT, GLOBAL_METER); // Tokenized data retried
+counter_metric!(REAPED_DELETE_TOKENIZE_TASKS_COUNT, GLOBAL_METER); // Delete tasks completed as their tokenized data was already gone
+counter_metric!(TEMP_LOCKER_PURGE_SCANNED_TOKENS, GLOBAL_METER); // Tokens scanned while purging the tokens of a merchant
+counter_metric!(TEMP_LOCKER_PURGED_TOKENS, GLOBAL_METER); // Tokens deleted while purging the tokens of a merchant
+gauge_metric!(TEMP_LOCKER_TOKEN_COUNT, GLOBAL_METER); // Tokens of the counted merchants in the temporary locker by payment method
+gauge_metric!(TEMP_LOCKER_IN_FLIGHT_OPERATIONS, GLOBAL_METER); // Temporary locker operations waiting on redis
 
 counter_metric!(CUSTOMER_CREATED, GLOBAL_METER);
 counter_metric!(CUSTOMER_REDACTED, GLOBAL_METER);
@@ -88,6 +94,9 @@ counter_metric!(CONNECTOR_HTTP_STATUS_CODE_5XX_COUNT, GLOBAL_METER);
 counter_metric!(CARD_LOCKER_FAILURES, GLOBAL_METER);
 counter_metric!(CARD_LOCKER_SUCCESSFUL_RESPONSE, GLOBAL_METER);
 counter_metric!(TEMP_LOCKER_FAILURES, GLOBAL_METER);
+counter_metric!(TEMP_LOCKER_LEGACY_KEYS_MOVED, GLOBAL_METER); // Tokens moved from their unprefixed redis key on read
+counter_metric!(TEMP_LOCKER_REENCRYPTED_TOKENS, GLOBAL_METER); // Tokens re-encrypted with the primary key after being read with a legacy key
+counter_metric!(VAULT_CIRCUIT_OPEN, GLOBAL_METER);
 histogram_metric!(CARD_ADD_TIME, GLOBAL_METER);
 histogram_metric!(CARD_GET_TIME, GLOBAL_METER);
 histogram_metric!(CARD_DELETE_TIME, GLOBAL_METER);
@@ -139,3 +148,8 @@ counter_metric!(ACCESS_TOKEN_CACHE_MISS, GLOBAL_METER);
 
 // Metrics for In-memory cache
 gauge_metric!(CACHE_ENTRY_COUNT, GLOBAL_METER);
+
+histogram_metric!(CONSTRAINT_GRAPH_BUILD_TIME, GLOBAL_METER);
+gauge_metric!(CONSTRAINT_GRAPH_NODE_COUNT, GLOBAL_METER);
+gauge_metric!(CONSTRAINT_GRAPH_EDGE_COUNT, GLOBAL_METER);
+gauge_metric!(CONSTRAINT_GRAPH_DOMAIN_COUNT, GLOBAL_METER);
@@ -35,6 +35,49 @@ where
     result
 }
 
+/// Builds a constraint graph, recording the build time and the node, edge and domain counts of the
+/// built graph against the graph name. `get_graph` picks the graph out of the output of `build`.
+pub fn record_constraint_graph_build<'a, V, R>(
+    graph_name: &'static str,
+    build: impl FnOnce() -> R,
+    get_graph: impl FnOnce(&R) -> Option<&hyperswitch_constraint_graph::ConstraintGraph<'a, V>>,
+) -> R
+where
+    V: hyperswitch_constraint_graph::ValueNode,
+{
+    let start = std::time::Instant::now();
+    let result = build();
+    let build_time = start.elapsed();
+
+    let attributes = [add_attributes("graph_name", graph_name)];
+    super::CONSTRAINT_GRAPH_BUILD_TIME.record(
+        &super::CONTEXT,
+        build_time.as_secs_f64(),
+        &attributes,
+    );
+    if let Some(graph) = get_graph(&result) {
+        for (metric, count) in [
+            (
+                &super::CONSTRAINT_GRAPH_NODE_COUNT,
+                graph.live_nodes().count(),
+            ),
+            (
+                &super::CONSTRAINT_GRAPH_EDGE_COUNT,
+                graph.live_edges().count(),
+            ),
+            (&super::CONSTRAINT_GRAPH_DOMAIN_COUNT, graph.domain.len()),
+        ] {
+            metric.observe(
+                &super::CONTEXT,
+                u64::try_from(count).unwrap_or(u64::MAX),
+                &attributes,
+            );
+        }
+    }
+
+    result
+}
+
 pub fn add_attributes<T: Into<opentelemetry::Value>>(
     key: &'static str,
     value: T,
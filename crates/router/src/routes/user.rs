@@ -656,7 +656,7 @@ pub async fn totp_verify(
         state.clone(),
         &req,
         json_payload.into_inner(),
-        |state, user, req_body, _| user_core::verify_totp(state, user, req_body),
+        |state, user, req_body, req_state| user_core::verify_totp(state, user, req_body, req_state),
         &auth::SinglePurposeOrLoginTokenAuth(TokenPurpose::TOTP),
         api_locking::LockAction::NotApplicable,
     ))
@@ -692,7 +692,7 @@ pub async fn totp_update(
         state.clone(),
         &req,
         json_payload.into_inner(),
-        |state, user, req_body, _| user_core::update_totp(state, user, req_body),
+        |state, user, req_body, req_state| user_core::update_totp(state, user, req_body, req_state),
         &auth::SinglePurposeOrLoginTokenAuth(TokenPurpose::TOTP),
         api_locking::LockAction::NotApplicable,
     ))
@@ -15,7 +15,7 @@ use actix_web::{
     body, http::header::HeaderValue, web, FromRequest, HttpRequest, HttpResponse, Responder,
     ResponseError,
 };
-use api_models::enums::{CaptureMethod, PaymentMethodType};
+use api_models::enums::{CaptureMethod, Currency, PaymentMethod, PaymentMethodType};
 pub use client::{proxy_bypass_urls, ApiClient, MockApiClient, ProxyClient};
 pub use common_utils::request::{ContentType, Method, Request, RequestBuilder};
 use common_utils::{
@@ -74,6 +74,78 @@ where
     }
 }
 
+/// Flows a connector has an implementation for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ConnectorFlow {
+    Authorize,
+    Capture,
+    Void,
+    PSync,
+    Refund,
+    RSync,
+}
+
+/// Describes what a connector is able to process, so that routing can skip connectors which
+/// cannot handle a given combination
+#[derive(Debug, Clone)]
+pub struct ConnectorSpecifications {
+    pub supported_payment_methods: Vec<(PaymentMethod, Vec<PaymentMethodType>)>,
+    /// `None` when the connector does not restrict the currency
+    pub supported_currencies: Option<Vec<Currency>>,
+    pub supported_capture_methods: Vec<CaptureMethod>,
+    pub supported_flows: Vec<ConnectorFlow>,
+    /// Whether a flow can be retried after a timeout, flows which are not listed are never retried
+    pub flow_retry_safety: Vec<(ConnectorFlow, RetrySafety)>,
+    /// Whether a void can reverse only a part of the authorization
+    pub supports_partial_void: bool,
+}
+
+/// Whether retrying a request whose outcome is unknown (e.g. on timeout) can cause it to be
+/// processed twice by the connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetrySafety {
+    /// The request has no side effects at the connector, or repeating it has no further effect
+    Safe,
+    /// The request is safe to retry only if the connector deduplicates it using a deterministic
+    /// idempotency key
+    RequiresIdempotencyKey,
+}
+
+impl ConnectorSpecifications {
+    pub fn supports(
+        &self,
+        payment_method: PaymentMethod,
+        payment_method_type: Option<PaymentMethodType>,
+        currency: Currency,
+        flow: ConnectorFlow,
+    ) -> bool {
+        let payment_method_supported =
+            self.supported_payment_methods
+                .iter()
+                .any(|(supported_pm, supported_pmts)| {
+                    *supported_pm == payment_method
+                        && payment_method_type.map_or(true, |pmt| supported_pmts.contains(&pmt))
+                });
+        let currency_supported = self
+            .supported_currencies
+            .as_ref()
+            .map_or(true, |currencies| currencies.contains(&currency));
+
+        payment_method_supported && currency_supported && self.supported_flows.contains(&flow)
+    }
+
+    pub fn is_retry_safe(&self, flow: ConnectorFlow, idempotency_key_sent: bool) -> bool {
+        self.flow_retry_safety
+            .iter()
+            .find(|(retry_flow, _)| *retry_flow == flow)
+            .is_some_and(|(_, retry_safety)| match retry_safety {
+                RetrySafety::Safe => true,
+                RetrySafety::RequiresIdempotencyKey => idempotency_key_sent,
+            })
+    }
+}
+
 pub trait ConnectorValidation: ConnectorCommon {
     fn validate_capture_method(
         &self,
@@ -127,6 +199,11 @@ pub trait ConnectorValidation: ConnectorCommon {
     fn is_webhook_source_verification_mandatory(&self) -> bool {
         false
     }
+
+    /// Returns the capabilities of the connector, `None` if they have not been described
+    fn get_connector_specifications(&self) -> Option<ConnectorSpecifications> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -1084,6 +1084,7 @@ impl SignInWithMultipleRolesStrategy {
                     .iter()
                     .map(|role| role.merchant_id.clone())
                     .collect(),
+                state.conf.merchant_key_store.decryption_concurrency,
             )
             .await
             .change_context(UserErrors::InternalServerError)?;
@@ -6,7 +6,8 @@ use totp_rs::{Algorithm, TOTP};
 use crate::{
     consts,
     core::errors::{UserErrors, UserResult},
-    routes::SessionState,
+    events::audit_events::{AuditEvent, AuditEventType, TwoFactorAuthMethod},
+    routes::{app::ReqState, SessionState},
 };
 
 pub fn generate_default_totp(
@@ -32,6 +33,27 @@ pub fn generate_default_totp(
     .change_context(UserErrors::InternalServerError)
 }
 
+/// Verifies a user submitted TOTP code against the secret, accepting codes of up to `tolerance`
+/// time steps before or after the current one. Codes that are not exactly `TOTP_DIGITS` digits,
+/// ignoring surrounding whitespace, never match.
+pub fn verify_totp(
+    email: pii::Email,
+    secret: masking::Secret<String>,
+    code: &masking::Secret<String>,
+    issuer: String,
+    tolerance: u8,
+) -> UserResult<bool> {
+    let code = code.peek().trim();
+    if code.len() != consts::user::TOTP_DIGITS || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(false);
+    }
+
+    let mut totp = generate_default_totp(email, Some(secret), issuer)?;
+    totp.skew = tolerance;
+    totp.check_current(code)
+        .change_context(UserErrors::InternalServerError)
+}
+
 pub async fn check_totp_in_redis(state: &SessionState, user_id: &str) -> UserResult<bool> {
     let redis_conn = super::get_redis_connection(state)?;
     let key = format!("{}{}", consts::user::REDIS_TOTP_PREFIX, user_id);
@@ -63,6 +85,26 @@ pub async fn insert_totp_in_redis(state: &SessionState, user_id: &str) -> UserRe
         .change_context(UserErrors::InternalServerError)
 }
 
+/// Marks the user as having completed TOTP verification and emits an audit event for it, in the
+/// event context of the request
+pub async fn record_totp_success(
+    state: &SessionState,
+    req_state: &ReqState,
+    user_id: &str,
+) -> UserResult<()> {
+    insert_totp_in_redis(state, user_id).await?;
+
+    req_state
+        .event_context
+        .event(AuditEvent::new(AuditEventType::TwoFactorAuthSuccess {
+            user_id: user_id.to_string(),
+            method: TwoFactorAuthMethod::Totp,
+        }))
+        .emit();
+
+    Ok(())
+}
+
 pub async fn insert_totp_secret_in_redis(
     state: &SessionState,
     user_id: &str,
@@ -139,3 +181,51 @@ pub async fn delete_recovery_code_from_redis(
         .change_context(UserErrors::InternalServerError)
         .map(|_| ())
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_totp() {
+        let email = pii::Email::from_str("user@example.com").unwrap();
+        let issuer = "Hyperswitch".to_string();
+        let totp = generate_default_totp(email.clone(), None, issuer.clone()).unwrap();
+        let secret = masking::Secret::new(totp.get_secret_base32());
+        let verify = |code: &str| {
+            verify_totp(
+                email.clone(),
+                secret.clone(),
+                &masking::Secret::new(code.to_string()),
+                issuer.clone(),
+                consts::user::TOTP_TOLERANCE,
+            )
+            .unwrap()
+        };
+
+        let current_code = totp.generate_current().unwrap();
+        assert!(verify(&current_code));
+        assert!(verify(&format!(" {current_code}\n")));
+
+        let previous_code = totp.generate(
+            totp.next_step_current()
+                .unwrap()
+                .saturating_sub(2 * consts::user::TOTP_VALIDITY_DURATION_IN_SECONDS),
+        );
+        assert!(verify(&previous_code));
+
+        let stale_code = totp.generate(
+            totp.next_step_current()
+                .unwrap()
+                .saturating_sub(4 * consts::user::TOTP_VALIDITY_DURATION_IN_SECONDS),
+        );
+        assert!(!verify(&stale_code));
+
+        assert!(!verify("12345"));
+        assert!(!verify("12345a"));
+        assert!(!verify(""));
+    }
+}
@@ -69,6 +69,10 @@ pub enum Flow {
     MerchantsAccountUpdate,
     /// Merchants account delete flow.
     MerchantsAccountDelete,
+    /// Merchant temporary locker tokens purge flow.
+    MerchantTokensPurge,
+    /// Merchant temporary locker token counts flow.
+    MerchantTokenCounts,
     /// Merchant Connectors create flow.
     MerchantConnectorsCreate,
     /// Merchant Connectors retrieve flow.
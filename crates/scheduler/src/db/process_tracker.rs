@@ -62,6 +62,12 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         status: storage_enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+    async fn find_processes_by_runner_business_status(
+        &self,
+        runner: storage::process_tracker::ProcessTrackerRunner,
+        business_status: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -106,6 +112,23 @@ impl ProcessTrackerInterface for Store {
         .map_err(|error| report!(errors::StorageError::from(error)))
     }
 
+    async fn find_processes_by_runner_business_status(
+        &self,
+        runner: storage::process_tracker::ProcessTrackerRunner,
+        business_status: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_processes_by_runner_business_status(
+            &conn,
+            &runner.to_string(),
+            business_status,
+            limit,
+        )
+        .await
+        .map_err(|error| report!(errors::StorageError::from(error)))
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
@@ -230,6 +253,32 @@ impl ProcessTrackerInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn find_processes_by_runner_business_status(
+        &self,
+        runner: storage::process_tracker::ProcessTrackerRunner,
+        business_status: &str,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.runner.as_deref() == Some(runner.to_string().as_str())
+                    && process.business_status == business_status
+            })
+            .take(
+                limit
+                    .and_then(|limit| usize::try_from(limit).ok())
+                    .unwrap_or(usize::MAX),
+            )
+            .cloned()
+            .collect();
+
+        Ok(processes)
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
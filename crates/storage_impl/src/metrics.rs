@@ -11,3 +11,6 @@ counter_metric!(KV_OPERATION_FAILED, GLOBAL_METER);
 counter_metric!(KV_PUSHED_TO_DRAINER, GLOBAL_METER);
 counter_metric!(KV_FAILED_TO_PUSH_TO_DRAINER, GLOBAL_METER);
 counter_metric!(KV_SOFT_KILL_ACTIVE_UPDATE, GLOBAL_METER);
+
+// Metrics for in-memory cache
+counter_metric!(IN_MEMORY_CACHE_TYPE_DOMINANT, GLOBAL_METER);
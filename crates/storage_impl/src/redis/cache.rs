@@ -1,4 +1,10 @@
-use std::{any::Any, borrow::Cow, fmt::Debug, sync::Arc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
 
 use common_utils::{
     errors::{self, CustomResult},
@@ -13,6 +19,7 @@ use router_env::tracing::{self, instrument};
 
 use crate::{
     errors::StorageError,
+    metrics,
     redis::{PubSubInterface, RedisConnInterface},
 };
 
@@ -52,12 +59,19 @@ const CACHE_TTI: u64 = 10 * 60;
 /// Max Capacity of Cache in MB
 const MAX_CAPACITY: u64 = 30;
 
+/// Percentage of the tracked entries above which a single type is reported as dominating the cache
+const DOMINANT_TYPE_SHARE_PERCENTAGE: u64 = 50;
+
+/// Minimum number of tracked entries before a type is reported as dominating the cache
+const DOMINANT_TYPE_MIN_ENTRIES: u64 = 100;
+
 /// Config Cache with time_to_live as 30 mins and time_to_idle as 10 mins.
-pub static CONFIG_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new(CACHE_TTL, CACHE_TTI, None));
+pub static CONFIG_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new_with_optional_type_tracking(CACHE_TTL, CACHE_TTI, None));
 
 /// Accounts cache with time_to_live as 30 mins and size limit
 pub static ACCOUNTS_CACHE: Lazy<Cache> =
-    Lazy::new(|| Cache::new(CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+    Lazy::new(|| Cache::new_with_optional_type_tracking(CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
 
 /// Routing Cache
 pub static ROUTING_CACHE: Lazy<Cache> =
@@ -82,6 +96,7 @@ pub static PM_FILTERS_CGRAPH_CACHE: Lazy<Cache> =
 /// Trait which defines the behaviour of types that's gonna be stored in Cache
 pub trait Cacheable: Any + Send + Sync + DynClone {
     fn as_any(&self) -> &dyn Any;
+    fn type_name(&self) -> &'static str;
 }
 
 pub enum CacheKind<'a> {
@@ -145,12 +160,70 @@ where
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 dyn_clone::clone_trait_object!(Cacheable);
 
 pub struct Cache {
     inner: MokaCache<String, Arc<dyn Cacheable>>,
+    type_entry_counts: Option<Arc<TypeEntryCounts>>,
+}
+
+/// Number of entries held in a cache for each cached type
+#[derive(Default)]
+struct TypeEntryCounts(Mutex<HashMap<&'static str, u64>>);
+
+impl TypeEntryCounts {
+    fn record_insert(&self, type_name: &'static str) {
+        let Ok(mut counts) = self.0.lock() else {
+            return;
+        };
+        let type_count = counts
+            .entry(type_name)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+        let type_count = *type_count;
+        let total_count: u64 = counts.values().sum();
+
+        if total_count >= DOMINANT_TYPE_MIN_ENTRIES
+            && type_count * 100 > total_count * DOMINANT_TYPE_SHARE_PERCENTAGE
+        {
+            router_env::logger::warn!(
+                type_name,
+                type_count,
+                total_count,
+                "Single type dominates the in-memory cache"
+            );
+            metrics::IN_MEMORY_CACHE_TYPE_DOMINANT.add(
+                &metrics::CONTEXT,
+                1,
+                &[router_env::opentelemetry::KeyValue::new(
+                    "type_name",
+                    type_name,
+                )],
+            );
+        }
+    }
+
+    fn record_removal(&self, type_name: &'static str) {
+        if let Ok(mut counts) = self.0.lock() {
+            if let Some(count) = counts.get_mut(type_name) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    fn get(&self, type_name: &'static str) -> u64 {
+        self.0
+            .lock()
+            .ok()
+            .and_then(|counts| counts.get(type_name).copied())
+            .unwrap_or(0)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -177,6 +250,46 @@ impl Cache {
     /// `time_to_idle`: Time in seconds before a `get` or `insert` operation an object is stored in a caching system before it's deleted
     /// `max_capacity`: Max size in MB's that the cache can hold
     pub fn new(time_to_live: u64, time_to_idle: u64, max_capacity: Option<u64>) -> Self {
+        Self::build(time_to_live, time_to_idle, max_capacity, None)
+    }
+
+    /// Same as `new`, but additionally tracks the number of entries per cached type and emits a
+    /// metric when a single type dominates the cache.
+    ///
+    /// Moka does not expose the recency of entries, so eviction is still left to its own policy.
+    pub fn new_with_type_tracking(
+        time_to_live: u64,
+        time_to_idle: u64,
+        max_capacity: Option<u64>,
+    ) -> Self {
+        Self::build(
+            time_to_live,
+            time_to_idle,
+            max_capacity,
+            Some(Arc::new(TypeEntryCounts::default())),
+        )
+    }
+
+    /// Tracks the entries per type only when the `cache_type_tracking` feature is enabled, for
+    /// the caches most likely to be bloated by a single type.
+    fn new_with_optional_type_tracking(
+        time_to_live: u64,
+        time_to_idle: u64,
+        max_capacity: Option<u64>,
+    ) -> Self {
+        if cfg!(feature = "cache_type_tracking") {
+            Self::new_with_type_tracking(time_to_live, time_to_idle, max_capacity)
+        } else {
+            Self::new(time_to_live, time_to_idle, max_capacity)
+        }
+    }
+
+    fn build(
+        time_to_live: u64,
+        time_to_idle: u64,
+        max_capacity: Option<u64>,
+        type_entry_counts: Option<Arc<TypeEntryCounts>>,
+    ) -> Self {
         let mut cache_builder = MokaCache::builder()
             .time_to_live(std::time::Duration::from_secs(time_to_live))
             .time_to_idle(std::time::Duration::from_secs(time_to_idle));
@@ -185,12 +298,23 @@ impl Cache {
             cache_builder = cache_builder.max_capacity(capacity * 1024 * 1024);
         }
 
+        if let Some(counts) = type_entry_counts.clone() {
+            cache_builder =
+                cache_builder.eviction_listener(move |_key, value: Arc<dyn Cacheable>, _cause| {
+                    counts.record_removal((*value).type_name())
+                });
+        }
+
         Self {
             inner: cache_builder.build(),
+            type_entry_counts,
         }
     }
 
     pub async fn push<T: Cacheable>(&self, key: CacheKey, val: T) {
+        if let Some(type_entry_counts) = &self.type_entry_counts {
+            type_entry_counts.record_insert(std::any::type_name::<T>());
+        }
         self.inner.insert(key.into(), Arc::new(val)).await;
     }
 
@@ -217,6 +341,36 @@ impl Cache {
     pub fn get_entry_count(&self) -> u64 {
         self.inner.entry_count()
     }
+
+    /// Returns an approximate number of entries of type `T` in this cache, `None` if the cache
+    /// does not track entries per type.
+    pub fn get_entry_count_for_type<T: Cacheable>(&self) -> Option<u64> {
+        self.type_entry_counts
+            .as_ref()
+            .map(|counts| counts.get(std::any::type_name::<T>()))
+    }
+
+    /// Returns the keys of the entries currently in the cache, for diagnosing stale entries.
+    /// Values are never returned as they may hold decrypted data.
+    ///
+    /// The prefix is split off at the first `:`, so keys pushed with an empty prefix which
+    /// contain a `:` are reported with the part before it as the prefix.
+    #[cfg(feature = "cache_diagnostics")]
+    pub fn get_keys(&self) -> Vec<CacheKey> {
+        self.inner
+            .iter()
+            .map(|(key, _)| match key.split_once(':') {
+                Some((prefix, key)) => CacheKey {
+                    key: key.to_string(),
+                    prefix: prefix.to_string(),
+                },
+                None => CacheKey {
+                    key: key.to_string(),
+                    prefix: String::new(),
+                },
+            })
+            .collect()
+    }
 }
 
 #[instrument(skip_all)]
@@ -465,6 +619,69 @@ mod cache_tests {
         );
     }
 
+    #[cfg(feature = "cache_diagnostics")]
+    #[tokio::test]
+    async fn get_keys_of_cache() {
+        let cache = Cache::new(1800, 1800, None);
+        cache
+            .push(
+                CacheKey {
+                    key: "key".to_string(),
+                    prefix: "prefix".to_string(),
+                },
+                "val".to_string(),
+            )
+            .await;
+
+        let keys = cache.get_keys();
+        assert_eq!(keys.len(), 1);
+        assert!(keys
+            .iter()
+            .all(|cache_key| cache_key.key == "key" && cache_key.prefix == "prefix"));
+    }
+
+    #[tokio::test]
+    async fn type_entry_counts_of_cache() {
+        let cache = Cache::new_with_type_tracking(1800, 1800, None);
+        for key in ["key1", "key2"] {
+            cache
+                .push(
+                    CacheKey {
+                        key: key.to_string(),
+                        prefix: "prefix".to_string(),
+                    },
+                    "val".to_string(),
+                )
+                .await;
+        }
+        cache
+            .push(
+                CacheKey {
+                    key: "key3".to_string(),
+                    prefix: "prefix".to_string(),
+                },
+                1_u32,
+            )
+            .await;
+
+        assert_eq!(cache.get_entry_count_for_type::<String>(), Some(2));
+        assert_eq!(cache.get_entry_count_for_type::<u32>(), Some(1));
+
+        cache
+            .remove(CacheKey {
+                key: "key1".to_string(),
+                prefix: "prefix".to_string(),
+            })
+            .await;
+        cache.run_pending_tasks().await;
+
+        assert_eq!(cache.get_entry_count_for_type::<String>(), Some(1));
+        assert_eq!(
+            Cache::new(1800, 1800, None).get_entry_count_for_type::<String>(),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn eviction_on_time_test() {
         let cache = Cache::new(2, 2, None);